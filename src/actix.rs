@@ -0,0 +1,258 @@
+//! Integration with `actix-web`.
+//!
+//! [`WebAppInitData`] and [`WebAppUser`] implement [`FromRequest`]. The data is validated with
+//! the [`Validator`] registered as `web::Data<Validator>`, and located as configured by the
+//! [`InitDataExtractor`] registered as app data, if any.
+
+use std::borrow::Cow;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+
+use actix_web::dev::Payload;
+use actix_web::http::StatusCode;
+use actix_web::http::header::{self, HeaderName};
+use actix_web::web::{Bytes, Data};
+use actix_web::{FromRequest, HttpRequest, HttpResponse, ResponseError};
+use serde::{Serialize, Serializer};
+
+use crate::error::serialize_error;
+use crate::{Error, Validator, WebAppInitData, WebAppUser, source};
+
+/// A place where initData may be found.
+///
+/// The same as [`http::InitDataSource`](crate::http::InitDataSource), for the header types of
+/// `actix-web`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum InitDataSource {
+    /// The `Authorization: tma <initData>` header.
+    Authorization,
+    /// A header containing the bare initData.
+    Header(HeaderName),
+    /// A query parameter.
+    Query(String),
+    /// A cookie, with a percent-encoded value.
+    Cookie(String),
+    /// A field of an `application/x-www-form-urlencoded` body.
+    FormField(String),
+    /// A string field of a JSON object body.
+    JsonField(String),
+}
+
+/// Locates initData in an ordered list of sources.
+///
+/// The default extractor only looks at the `Authorization: tma <initData>` header. If a body
+/// source is used, the body is consumed and cannot be extracted again by the handler.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct InitDataExtractor {
+    sources: Vec<InitDataSource>,
+}
+
+impl Default for InitDataExtractor {
+    fn default() -> Self {
+        Self {
+            sources: vec![InitDataSource::Authorization],
+        }
+    }
+}
+
+impl InitDataExtractor {
+    /// An extractor without any sources.
+    pub fn empty() -> Self {
+        Self {
+            sources: Vec::new(),
+        }
+    }
+
+    /// Try `source` after the sources added before.
+    pub fn source(mut self, source: InitDataSource) -> Self {
+        self.sources.push(source);
+        self
+    }
+
+    pub fn authorization(self) -> Self {
+        self.source(InitDataSource::Authorization)
+    }
+
+    pub fn header(self, name: HeaderName) -> Self {
+        self.source(InitDataSource::Header(name))
+    }
+
+    pub fn query(self, name: impl Into<String>) -> Self {
+        self.source(InitDataSource::Query(name.into()))
+    }
+
+    pub fn cookie(self, name: impl Into<String>) -> Self {
+        self.source(InitDataSource::Cookie(name.into()))
+    }
+
+    pub fn form_field(self, name: impl Into<String>) -> Self {
+        self.source(InitDataSource::FormField(name.into()))
+    }
+
+    pub fn json_field(self, name: impl Into<String>) -> Self {
+        self.source(InitDataSource::JsonField(name.into()))
+    }
+
+    pub fn sources(&self) -> &[InitDataSource] {
+        &self.sources
+    }
+
+    fn has_body_source(&self) -> bool {
+        self.sources.iter().any(|x| {
+            matches!(
+                x,
+                InitDataSource::FormField(_) | InitDataSource::JsonField(_)
+            )
+        })
+    }
+}
+
+/// The error returned when initData cannot be extracted.
+#[derive(Debug)]
+pub enum TelegramAuthRejection {
+    /// No `web::Data<Validator>` is registered.
+    MissingValidator,
+    /// The initData was not found in any of the sources.
+    NotPresent,
+    /// The initData is invalid.
+    Invalid(Error),
+}
+
+impl FromRequest for WebAppInitData {
+    type Error = TelegramAuthRejection;
+    type Future = Pin<Box<dyn Future<Output = Result<Self, Self::Error>>>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let req = req.clone();
+        let extractor = req
+            .app_data::<InitDataExtractor>()
+            .cloned()
+            .unwrap_or_default();
+        let mut payload = if extractor.has_body_source() {
+            payload.take()
+        } else {
+            Payload::None
+        };
+
+        Box::pin(async move {
+            let validator = req
+                .app_data::<Data<Validator>>()
+                .ok_or(TelegramAuthRejection::MissingValidator)?;
+            let mut body = None;
+            for source in &extractor.sources {
+                if body.is_none()
+                    && matches!(
+                        source,
+                        InitDataSource::FormField(_) | InitDataSource::JsonField(_)
+                    )
+                {
+                    body = Some(Bytes::from_request(&req, &mut payload).await.ok());
+                }
+                let bytes = body.as_ref().and_then(Option::as_deref);
+                let init_data = match source {
+                    InitDataSource::Authorization => req
+                        .headers()
+                        .get(header::AUTHORIZATION)
+                        .and_then(|x| x.to_str().ok())
+                        .and_then(source::authorization)
+                        .map(Cow::Borrowed),
+                    InitDataSource::Header(name) => req
+                        .headers()
+                        .get(name)
+                        .and_then(|x| x.to_str().ok())
+                        .map(Cow::Borrowed),
+                    InitDataSource::Query(name) => {
+                        source::form_field(req.query_string().as_bytes(), name)
+                    }
+                    InitDataSource::Cookie(name) => source::cookie(
+                        req.headers()
+                            .get_all(header::COOKIE)
+                            .filter_map(|x| x.to_str().ok()),
+                        name,
+                    ),
+                    InitDataSource::FormField(name) => {
+                        bytes.and_then(|x| source::form_field(x, name))
+                    }
+                    InitDataSource::JsonField(name) => bytes
+                        .and_then(|x| source::json_field(x, name))
+                        .map(Cow::Owned),
+                };
+                if let Some(init_data) = init_data {
+                    return validator
+                        .validate(init_data.as_bytes())
+                        .map_err(TelegramAuthRejection::Invalid);
+                }
+            }
+            Err(TelegramAuthRejection::NotPresent)
+        })
+    }
+}
+
+/// Requires the initData to contain the `user` field.
+impl FromRequest for WebAppUser {
+    type Error = TelegramAuthRejection;
+    type Future = Pin<Box<dyn Future<Output = Result<Self, Self::Error>>>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let init_data = WebAppInitData::from_request(req, payload);
+        Box::pin(async move {
+            init_data
+                .await?
+                .user
+                .ok_or(TelegramAuthRejection::Invalid(Error::MissingField("user")))
+        })
+    }
+}
+
+impl fmt::Display for TelegramAuthRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingValidator => f.write_str("validator is not configured"),
+            Self::NotPresent => f.write_str("missing init data"),
+            Self::Invalid(e) => write!(f, "invalid init data: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for TelegramAuthRejection {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Invalid(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Serializes like [`Error`], with the `missing_validator` and `not_present` codes for the errors
+/// other than [`Invalid`](Self::Invalid).
+impl Serialize for TelegramAuthRejection {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::MissingValidator => serialize_error(serializer, "missing_validator", None, self),
+            Self::NotPresent => serialize_error(serializer, "not_present", None, self),
+            Self::Invalid(e) => e.serialize(serializer),
+        }
+    }
+}
+
+impl ResponseError for TelegramAuthRejection {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            Self::MissingValidator => StatusCode::INTERNAL_SERVER_ERROR,
+            Self::NotPresent => StatusCode::UNAUTHORIZED,
+            Self::Invalid(e) if e.is_unauthorized() => StatusCode::UNAUTHORIZED,
+            Self::Invalid(_) => StatusCode::BAD_REQUEST,
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        let mut response = HttpResponse::build(self.status_code());
+        if self.status_code() == StatusCode::UNAUTHORIZED {
+            response.insert_header((header::WWW_AUTHENTICATE, "tma"));
+        }
+        response
+            .content_type("application/json")
+            .body(serde_json::to_string(self).expect("serialization cannot fail"))
+    }
+}
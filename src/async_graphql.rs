@@ -0,0 +1,123 @@
+//! Integration with `async-graphql`.
+//!
+//! Use [`authenticate`] to validate initData of an HTTP request and insert the verified
+//! [`WebAppInitData`] into the GraphQL request data, and [`TelegramGuard`] to require it on
+//! fields:
+//!
+//! ```ignore
+//! #[graphql(guard = "TelegramGuard::user().premium().not_bot()")]
+//! async fn secret(&self) -> &str { ... }
+//! ```
+
+use std::borrow::Cow;
+
+use async_graphql::{Context, ErrorExtensions, Guard, Request};
+use http::HeaderMap;
+use http::request::Parts;
+
+use crate::http::InitDataExtractor;
+use crate::{Error, Validator, WebAppInitData, WebAppUser};
+
+/// Validate initData from the `Authorization: tma <initData>` header and insert the verified
+/// [`WebAppInitData`] into the request data.
+///
+/// A request without the header is returned as is, leaving it to [`TelegramGuard`] to reject it.
+pub fn authenticate(
+    request: Request,
+    validator: &Validator,
+    headers: &HeaderMap,
+) -> Result<Request, Error> {
+    insert(
+        request,
+        validator,
+        InitDataExtractor::default().extract_headers(headers),
+    )
+}
+
+/// Like [`authenticate`], but locate initData in the HTTP request head with `extractor`.
+pub fn authenticate_with(
+    request: Request,
+    validator: &Validator,
+    extractor: &InitDataExtractor,
+    parts: &Parts,
+) -> Result<Request, Error> {
+    insert(request, validator, extractor.extract_parts(parts))
+}
+
+fn insert(
+    request: Request,
+    validator: &Validator,
+    init_data: Option<Cow<'_, str>>,
+) -> Result<Request, Error> {
+    let Some(init_data) = init_data else {
+        return Ok(request);
+    };
+    let data = validator.validate(init_data.as_bytes())?;
+    Ok(request.data(data))
+}
+
+/// A [`Guard`] requiring verified initData with the `user` field in the context.
+///
+/// Fails with the `UNAUTHENTICATED` error code if there is no such user, and with `FORBIDDEN` if
+/// the user does not satisfy the requirements.
+#[derive(Debug, Clone, Copy)]
+pub struct TelegramGuard {
+    premium: bool,
+    not_bot: bool,
+    predicate: Option<fn(&WebAppUser) -> bool>,
+}
+
+impl TelegramGuard {
+    pub fn user() -> Self {
+        Self {
+            premium: false,
+            not_bot: false,
+            predicate: None,
+        }
+    }
+
+    /// Require the user to satisfy `predicate`, e.g. to be in an allow list.
+    pub fn custom(predicate: fn(&WebAppUser) -> bool) -> Self {
+        Self {
+            predicate: Some(predicate),
+            ..Self::user()
+        }
+    }
+
+    /// Also require [`WebAppUser::is_premium`](crate::WebAppUser::is_premium).
+    pub fn premium(mut self) -> Self {
+        self.premium = true;
+        self
+    }
+
+    /// Also require the user not to be a bot.
+    pub fn not_bot(mut self) -> Self {
+        self.not_bot = true;
+        self
+    }
+}
+
+impl Guard for TelegramGuard {
+    async fn check(&self, ctx: &Context<'_>) -> async_graphql::Result<()> {
+        let Some(user) = ctx
+            .data_opt::<WebAppInitData>()
+            .and_then(WebAppInitData::user)
+        else {
+            return Err(error("not authenticated", "UNAUTHENTICATED"));
+        };
+        if self.premium && !user.is_premium() {
+            return Err(error("premium user required", "FORBIDDEN"));
+        }
+        if self.not_bot && user.is_bot() == Some(true) {
+            return Err(error("bots are not allowed", "FORBIDDEN"));
+        }
+        if self.predicate.is_some_and(|x| !x(user)) {
+            return Err(error("user is not allowed", "FORBIDDEN"));
+        }
+        Ok(())
+    }
+}
+
+fn error(message: &str, code: &str) -> async_graphql::Error {
+    async_graphql::Error::new(message).extend_with(|_, e| e.set("code", code))
+}
@@ -0,0 +1,98 @@
+//! Integration with `axum`.
+
+use std::fmt;
+
+use axum_core::extract::{FromRef, FromRequestParts};
+use axum_core::response::{IntoResponse, Response};
+use http::request::Parts;
+use http::{HeaderValue, StatusCode, header};
+use serde::{Serialize, Serializer};
+
+use crate::error::serialize_error;
+use crate::http::{ExtractError, InitDataExtractor};
+use crate::{Error, Validator, WebAppInitData};
+
+/// An extractor of initData, from the `Authorization: tma <initData>` header by default.
+///
+/// The data is validated with the [`Validator`] from the app state, and located with the
+/// [`InitDataExtractor`] from the request extensions, if any, e.g. added with
+/// `Extension(InitDataExtractor::empty().cookie("init_data"))`. Body sources are skipped.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TelegramAuth(pub WebAppInitData);
+
+/// The rejection of the [`TelegramAuth`] extractor.
+#[derive(Debug)]
+pub enum TelegramAuthRejection {
+    /// The initData was not found in any of the sources.
+    NotPresent,
+    /// The initData is invalid.
+    Invalid(Error),
+}
+
+impl<S> FromRequestParts<S> for TelegramAuth
+where
+    Validator: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = TelegramAuthRejection;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let validator = Validator::from_ref(state);
+        let result = match parts.extensions.get::<InitDataExtractor>() {
+            Some(extractor) => extractor.validate_parts(&validator, parts),
+            None => InitDataExtractor::default().validate_parts(&validator, parts),
+        };
+        match result {
+            Ok(data) => Ok(Self(data)),
+            Err(ExtractError::NotPresent) => Err(TelegramAuthRejection::NotPresent),
+            Err(ExtractError::Invalid(e)) => Err(TelegramAuthRejection::Invalid(e)),
+        }
+    }
+}
+
+impl fmt::Display for TelegramAuthRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotPresent => f.write_str("missing init data"),
+            Self::Invalid(e) => write!(f, "invalid init data: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for TelegramAuthRejection {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Invalid(e) => Some(e),
+            Self::NotPresent => None,
+        }
+    }
+}
+
+/// Serializes like [`Error`], with the `not_present` code if the initData is missing.
+impl Serialize for TelegramAuthRejection {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::NotPresent => serialize_error(serializer, "not_present", None, self),
+            Self::Invalid(e) => e.serialize(serializer),
+        }
+    }
+}
+
+impl IntoResponse for TelegramAuthRejection {
+    fn into_response(self) -> Response {
+        let status = match &self {
+            Self::NotPresent => StatusCode::UNAUTHORIZED,
+            Self::Invalid(e) if e.is_unauthorized() => StatusCode::UNAUTHORIZED,
+            Self::Invalid(_) => StatusCode::BAD_REQUEST,
+        };
+        let body = serde_json::to_string(&self).expect("serialization cannot fail");
+        let mut response =
+            (status, [(header::CONTENT_TYPE, "application/json")], body).into_response();
+        if status == StatusCode::UNAUTHORIZED {
+            response
+                .headers_mut()
+                .insert(header::WWW_AUTHENTICATE, HeaderValue::from_static("tma"));
+        }
+        response
+    }
+}
@@ -0,0 +1,215 @@
+use std::collections::BTreeMap;
+use std::time::{Duration, SystemTime};
+
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+
+use crate::signed_form::write_data_check_string;
+use crate::{ChatType, SignedForm, WebAppChat, WebAppUser};
+
+/// A builder of signed initData, for tests and local development.
+///
+/// The resulting query string is accepted by a [`Validator`](crate::Validator) created with the
+/// same token or, if an Ed25519 key is set, by [`Validator::third_party_with_key`] with the
+/// matching public key.
+///
+/// [`Validator::third_party_with_key`]: crate::Validator::third_party_with_key
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebAppInitDataBuilder {
+    fields: BTreeMap<String, String>,
+    ed25519: Option<(u64, [u8; 32])>,
+}
+
+impl Default for WebAppInitDataBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WebAppInitDataBuilder {
+    /// Create a builder with `auth_date` set to the current time.
+    pub fn new() -> Self {
+        Self {
+            fields: BTreeMap::new(),
+            ed25519: None,
+        }
+        .auth_date(SystemTime::now())
+    }
+
+    pub fn query_id(self, query_id: impl Into<String>) -> Self {
+        self.field("query_id", query_id)
+    }
+
+    pub fn user(self, user: &WebAppUser) -> Self {
+        self.json_field("user", user)
+    }
+
+    pub fn receiver(self, receiver: &WebAppUser) -> Self {
+        self.json_field("receiver", receiver)
+    }
+
+    pub fn chat(self, chat: &WebAppChat) -> Self {
+        self.json_field("chat", chat)
+    }
+
+    pub fn chat_type(self, chat_type: ChatType) -> Self {
+        self.field("chat_type", chat_type)
+    }
+
+    pub fn chat_instance(self, chat_instance: impl Into<String>) -> Self {
+        self.field("chat_instance", chat_instance)
+    }
+
+    pub fn start_param(self, start_param: impl Into<String>) -> Self {
+        self.field("start_param", start_param)
+    }
+
+    pub fn can_send_after(self, can_send_after: Duration) -> Self {
+        self.field("can_send_after", can_send_after.as_secs().to_string())
+    }
+
+    pub fn auth_date(self, auth_date: SystemTime) -> Self {
+        let secs = auth_date
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        self.field("auth_date", secs.to_string())
+    }
+
+    /// Set an arbitrary field, e.g. one not modeled by this crate.
+    pub fn field(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.fields.insert(key.into(), value.into());
+        self
+    }
+
+    /// Also add the `signature` field, signed by the Ed25519 key derived from `seed`.
+    ///
+    /// Use [`ed25519_public_key`] to get the matching public key.
+    pub fn ed25519_key(mut self, bot_id: u64, seed: [u8; 32]) -> Self {
+        self.ed25519 = Some((bot_id, seed));
+        self
+    }
+
+    /// Build the URL-encoded initData, signed with the bot token.
+    pub fn build(&self, token: &str) -> String {
+        let mut fields = self.fields.clone();
+        fields.remove("hash");
+        fields.remove("signature");
+
+        if let Some((bot_id, seed)) = self.ed25519 {
+            let key_pair = ed25519_compact::KeyPair::from_seed(ed25519_compact::Seed::new(seed));
+            let mut message = format!("{bot_id}:WebAppData\n");
+            write_data_check_string(&fields, &[], |x| message.push_str(x));
+            let signature = key_pair.sk.sign(message, None);
+            fields.insert("signature".into(), URL_SAFE_NO_PAD.encode(*signature));
+        }
+
+        let mut hmac = hmac_sha256::HMAC::new(SignedForm::web_app_secret_key(token));
+        write_data_check_string(&fields, &[], |x| hmac.update(x));
+        fields.insert("hash".into(), hex(&hmac.finalize()));
+
+        form_urlencoded::Serializer::new(String::new())
+            .extend_pairs(&fields)
+            .finish()
+    }
+
+    fn json_field(self, key: &str, value: &impl serde::Serialize) -> Self {
+        let json = serde_json::to_string(value).expect("serialization cannot fail");
+        self.field(key, json)
+    }
+}
+
+/// Constructors for use with [`WebAppInitDataBuilder`].
+impl WebAppUser {
+    pub fn new(id: i64, first_name: impl Into<String>) -> Self {
+        Self {
+            id,
+            is_bot: None,
+            first_name: first_name.into(),
+            last_name: None,
+            username: None,
+            language_code: None,
+            is_premium: false,
+            added_to_attachment_menu: false,
+            allows_write_to_pm: false,
+            photo_url: None,
+        }
+    }
+
+    pub fn with_is_bot(mut self, is_bot: bool) -> Self {
+        self.is_bot = Some(is_bot);
+        self
+    }
+
+    pub fn with_last_name(mut self, last_name: impl Into<String>) -> Self {
+        self.last_name = Some(last_name.into());
+        self
+    }
+
+    pub fn with_username(mut self, username: impl Into<String>) -> Self {
+        self.username = Some(username.into());
+        self
+    }
+
+    pub fn with_language_code(mut self, language_code: impl Into<String>) -> Self {
+        self.language_code = Some(language_code.into());
+        self
+    }
+
+    pub fn with_is_premium(mut self, is_premium: bool) -> Self {
+        self.is_premium = is_premium;
+        self
+    }
+
+    pub fn with_added_to_attachment_menu(mut self, added_to_attachment_menu: bool) -> Self {
+        self.added_to_attachment_menu = added_to_attachment_menu;
+        self
+    }
+
+    pub fn with_allows_write_to_pm(mut self, allows_write_to_pm: bool) -> Self {
+        self.allows_write_to_pm = allows_write_to_pm;
+        self
+    }
+
+    pub fn with_photo_url(mut self, photo_url: impl Into<String>) -> Self {
+        self.photo_url = Some(photo_url.into());
+        self
+    }
+}
+
+/// Constructors for use with [`WebAppInitDataBuilder`].
+impl WebAppChat {
+    pub fn new(id: i64, chat_type: ChatType, title: impl Into<String>) -> Self {
+        Self {
+            id,
+            chat_type,
+            title: title.into(),
+            username: None,
+            photo_url: None,
+        }
+    }
+
+    pub fn with_username(mut self, username: impl Into<String>) -> Self {
+        self.username = Some(username.into());
+        self
+    }
+
+    pub fn with_photo_url(mut self, photo_url: impl Into<String>) -> Self {
+        self.photo_url = Some(photo_url.into());
+        self
+    }
+}
+
+/// Get the Ed25519 public key corresponding to `seed`.
+pub fn ed25519_public_key(seed: [u8; 32]) -> [u8; 32] {
+    *ed25519_compact::KeyPair::from_seed(ed25519_compact::Seed::new(seed)).pk
+}
+
+fn hex(bytes: &[u8]) -> String {
+    let mut result = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        use std::fmt::Write;
+        let _ = write!(result, "{byte:02x}");
+    }
+    result
+}
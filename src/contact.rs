@@ -0,0 +1,78 @@
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Error, SignedForm, ValidationOptions, deserialize_unix_time, parse_unix_time, unix_time,
+};
+
+const KNOWN_FIELDS: &[&str] = &["contact", "auth_date", "hash"];
+
+/// A validated contact, shared by the user via `Telegram.WebApp.requestContact`.
+///
+/// The signed response has the form `contact=<json>&auth_date=<date>&hash=<hash>` and is
+/// validated with the bot token the same way as initData. Check that [`user_id`](Self::user_id)
+/// matches the user from the initData of the request.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub struct SharedContact {
+    user_id: i64,
+    phone_number: String,
+    first_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_name: Option<String>,
+    /// Not a part of the `contact` JSON, filled in after parsing it.
+    #[serde(default, deserialize_with = "deserialize_unix_time")]
+    auth_date: u64,
+}
+
+impl SharedContact {
+    pub fn new(token: &str, raw: &[u8]) -> Result<Self, Error> {
+        Self::new_with_options(token, raw, &ValidationOptions::default())
+    }
+
+    pub fn new_with_options(
+        token: &str,
+        raw: &[u8],
+        options: &ValidationOptions,
+    ) -> Result<Self, Error> {
+        let form = SignedForm::parse_known(raw, options, Some(KNOWN_FIELDS))?;
+        form.verify(&SignedForm::web_app_secret_key(token))?;
+        let mut fields = form.into_fields();
+        options.check_required(&fields)?;
+
+        let mut contact: SharedContact = serde_json::from_str(
+            &fields
+                .remove("contact")
+                .ok_or(Error::MissingField("contact"))?,
+        )
+        .map_err(|e| Error::InvalidJson("contact", e))?;
+        contact.auth_date = parse_unix_time(
+            "auth_date",
+            &fields
+                .remove("auth_date")
+                .ok_or(Error::MissingField("auth_date"))?,
+        )?;
+        options.check_freshness(contact.auth_date)?;
+        Ok(contact)
+    }
+
+    pub fn user_id(&self) -> i64 {
+        self.user_id
+    }
+
+    pub fn phone_number(&self) -> &str {
+        &self.phone_number
+    }
+
+    pub fn first_name(&self) -> &str {
+        &self.first_name
+    }
+
+    pub fn last_name(&self) -> Option<&str> {
+        self.last_name.as_deref()
+    }
+
+    pub fn auth_date(&self) -> SystemTime {
+        unix_time(self.auth_date).expect("auth_date is checked when parsing")
+    }
+}
@@ -0,0 +1,143 @@
+use std::fmt;
+
+#[cfg(feature = "serialize-error")]
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+
+#[derive(Debug)]
+pub enum Error {
+    /// The `hash` field is not a 64 character hex string.
+    MalformedHash,
+    /// The `hash` field does not match the data.
+    InvalidHash,
+    /// The `signature` field is not a base64url encoded Ed25519 signature.
+    MalformedSignature,
+    /// The `signature` field does not match the data.
+    InvalidSignature,
+    MissingField(&'static str),
+    InvalidJson(&'static str, serde_json::Error),
+    InvalidNumericField(&'static str),
+    DuplicateKey(String),
+    /// A key outside of the known initData fields, rejected in strict mode.
+    UnknownKey(String),
+    /// A `=value` pair without a key, rejected in strict mode.
+    EmptyKey,
+    TooLarge {
+        len: usize,
+        max: usize,
+    },
+    /// `auth_date` is too far in the past.
+    Expired,
+    /// `auth_date` is in the future.
+    AuthDateInFuture,
+}
+
+impl Error {
+    /// A stable, machine-readable code identifying the kind of the error.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::MalformedHash => "malformed_hash",
+            Self::InvalidHash => "invalid_hash",
+            Self::MalformedSignature => "malformed_signature",
+            Self::InvalidSignature => "invalid_signature",
+            Self::MissingField(_) => "missing_field",
+            Self::InvalidJson(_, _) => "invalid_json",
+            Self::InvalidNumericField(_) => "invalid_numeric_field",
+            Self::DuplicateKey(_) => "duplicate_key",
+            Self::UnknownKey(_) => "unknown_key",
+            Self::EmptyKey => "empty_key",
+            Self::TooLarge { .. } => "too_large",
+            Self::Expired => "expired",
+            Self::AuthDateInFuture => "auth_date_in_future",
+        }
+    }
+
+    /// Whether the error means the data is not authentic or no longer valid, as opposed to being
+    /// malformed.
+    ///
+    /// HTTP integrations map such errors to `401 Unauthorized` and the rest to `400 Bad Request`.
+    pub fn is_unauthorized(&self) -> bool {
+        match self {
+            Self::MalformedHash
+            | Self::InvalidHash
+            | Self::MalformedSignature
+            | Self::InvalidSignature
+            | Self::Expired
+            | Self::AuthDateInFuture => true,
+            Self::MissingField(field) => matches!(*field, "hash" | "signature"),
+            Self::InvalidJson(_, _)
+            | Self::InvalidNumericField(_)
+            | Self::DuplicateKey(_)
+            | Self::UnknownKey(_)
+            | Self::EmptyKey
+            | Self::TooLarge { .. } => false,
+        }
+    }
+
+    /// The name of the offending field, if the error is specific to one.
+    pub fn field(&self) -> Option<&str> {
+        match self {
+            Self::MissingField(field)
+            | Self::InvalidJson(field, _)
+            | Self::InvalidNumericField(field) => Some(field),
+            Self::DuplicateKey(key) | Self::UnknownKey(key) => Some(key),
+            Self::MalformedHash | Self::InvalidHash => Some("hash"),
+            Self::MalformedSignature | Self::InvalidSignature => Some("signature"),
+            Self::Expired | Self::AuthDateInFuture => Some("auth_date"),
+            Self::TooLarge { .. } | Self::EmptyKey => None,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MalformedHash => f.write_str("malformed hash"),
+            Self::InvalidHash => f.write_str("hash does not match the data"),
+            Self::MalformedSignature => f.write_str("malformed signature"),
+            Self::InvalidSignature => f.write_str("signature does not match the data"),
+            Self::MissingField(field) => write!(f, "missing field '{field}'"),
+            Self::InvalidJson(field, e) => write!(f, "invalid json in field '{field}': {e}"),
+            Self::InvalidNumericField(field) => write!(f, "field '{field}' is not a valid number"),
+            Self::DuplicateKey(key) => write!(f, "duplicate key '{key}'"),
+            Self::UnknownKey(key) => write!(f, "unknown key '{key}'"),
+            Self::EmptyKey => f.write_str("empty key"),
+            Self::TooLarge { len, max } => {
+                write!(f, "init data is too large ({len} bytes, max is {max})")
+            }
+            Self::Expired => f.write_str("init data has expired"),
+            Self::AuthDateInFuture => f.write_str("auth_date is in the future"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InvalidJson(_, e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Serializes as `{"code": ..., "field": ..., "message": ...}`.
+#[cfg(feature = "serialize-error")]
+impl Serialize for Error {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_error(serializer, self.code(), self.field(), self)
+    }
+}
+
+/// Serialize an error in the shape of [`Error`], for the errors of the integrations.
+#[cfg(feature = "serialize-error")]
+pub(crate) fn serialize_error<S: Serializer>(
+    serializer: S,
+    code: &str,
+    field: Option<&str>,
+    message: &dyn fmt::Display,
+) -> Result<S::Ok, S::Error> {
+    let mut s = serializer.serialize_struct("Error", 3)?;
+    s.serialize_field("code", code)?;
+    s.serialize_field("field", &field)?;
+    s.serialize_field("message", &message.to_string())?;
+    s.end()
+}
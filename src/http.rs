@@ -0,0 +1,199 @@
+//! Locating initData in `http` requests.
+
+use std::borrow::Cow;
+use std::fmt;
+
+use ::http::header::{self, HeaderMap, HeaderName};
+use ::http::request::Parts;
+use ::http::{Request, Uri};
+
+use crate::{Error, Validator, WebAppInitData, source};
+
+/// A place where initData may be found.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum InitDataSource {
+    /// The `Authorization: tma <initData>` header.
+    Authorization,
+    /// A header containing the bare initData.
+    Header(HeaderName),
+    /// A query parameter.
+    Query(String),
+    /// A cookie, with a percent-encoded value.
+    Cookie(String),
+    /// A field of an `application/x-www-form-urlencoded` body.
+    FormField(String),
+    /// A string field of a JSON object body.
+    JsonField(String),
+}
+
+/// Locates initData in an ordered list of sources.
+///
+/// The default extractor only looks at the `Authorization: tma <initData>` header.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct InitDataExtractor {
+    sources: Vec<InitDataSource>,
+}
+
+/// The error returned by [`InitDataExtractor::validate`] and friends.
+#[derive(Debug)]
+pub enum ExtractError {
+    /// The initData was not found in any of the sources.
+    NotPresent,
+    /// The initData is invalid.
+    Invalid(Error),
+}
+
+impl Default for InitDataExtractor {
+    fn default() -> Self {
+        Self {
+            sources: vec![InitDataSource::Authorization],
+        }
+    }
+}
+
+impl InitDataExtractor {
+    /// An extractor without any sources.
+    pub fn empty() -> Self {
+        Self {
+            sources: Vec::new(),
+        }
+    }
+
+    /// Try `source` after the sources added before.
+    pub fn source(mut self, source: InitDataSource) -> Self {
+        self.sources.push(source);
+        self
+    }
+
+    pub fn authorization(self) -> Self {
+        self.source(InitDataSource::Authorization)
+    }
+
+    pub fn header(self, name: HeaderName) -> Self {
+        self.source(InitDataSource::Header(name))
+    }
+
+    pub fn query(self, name: impl Into<String>) -> Self {
+        self.source(InitDataSource::Query(name.into()))
+    }
+
+    pub fn cookie(self, name: impl Into<String>) -> Self {
+        self.source(InitDataSource::Cookie(name.into()))
+    }
+
+    pub fn form_field(self, name: impl Into<String>) -> Self {
+        self.source(InitDataSource::FormField(name.into()))
+    }
+
+    pub fn json_field(self, name: impl Into<String>) -> Self {
+        self.source(InitDataSource::JsonField(name.into()))
+    }
+
+    pub fn sources(&self) -> &[InitDataSource] {
+        &self.sources
+    }
+
+    /// Find initData in a request, skipping the body sources.
+    pub fn extract<'a, B>(&self, req: &'a Request<B>) -> Option<Cow<'a, str>> {
+        self.find(req.headers(), Some(req.uri()), None)
+    }
+
+    /// Find initData in a request head, skipping the body sources.
+    pub fn extract_parts<'a>(&self, parts: &'a Parts) -> Option<Cow<'a, str>> {
+        self.find(&parts.headers, Some(&parts.uri), None)
+    }
+
+    /// Find initData in request headers, skipping the query and body sources.
+    pub fn extract_headers<'a>(&self, headers: &'a HeaderMap) -> Option<Cow<'a, str>> {
+        self.find(headers, None, None)
+    }
+
+    /// Find initData in a request head and its buffered body.
+    pub fn extract_with_body<'a>(&self, parts: &'a Parts, body: &'a [u8]) -> Option<Cow<'a, str>> {
+        self.find(&parts.headers, Some(&parts.uri), Some(body))
+    }
+
+    /// Find initData in a request, skipping the body sources, and validate it.
+    pub fn validate<B>(
+        &self,
+        validator: &Validator,
+        req: &Request<B>,
+    ) -> Result<WebAppInitData, ExtractError> {
+        let init_data = self.extract(req).ok_or(ExtractError::NotPresent)?;
+        validator
+            .validate(init_data.as_bytes())
+            .map_err(ExtractError::Invalid)
+    }
+
+    /// Find initData in a request head, skipping the body sources, and validate it.
+    pub fn validate_parts(
+        &self,
+        validator: &Validator,
+        parts: &Parts,
+    ) -> Result<WebAppInitData, ExtractError> {
+        let init_data = self.extract_parts(parts).ok_or(ExtractError::NotPresent)?;
+        validator
+            .validate(init_data.as_bytes())
+            .map_err(ExtractError::Invalid)
+    }
+
+    /// Find initData in a request head and its buffered body, and validate it.
+    pub fn validate_with_body(
+        &self,
+        validator: &Validator,
+        parts: &Parts,
+        body: &[u8],
+    ) -> Result<WebAppInitData, ExtractError> {
+        let init_data = self
+            .extract_with_body(parts, body)
+            .ok_or(ExtractError::NotPresent)?;
+        validator
+            .validate(init_data.as_bytes())
+            .map_err(ExtractError::Invalid)
+    }
+
+    fn find<'a>(
+        &self,
+        headers: &'a HeaderMap,
+        uri: Option<&'a Uri>,
+        body: Option<&'a [u8]>,
+    ) -> Option<Cow<'a, str>> {
+        self.sources.iter().find_map(|source| match source {
+            InitDataSource::Authorization => headers
+                .get(header::AUTHORIZATION)?
+                .to_str()
+                .ok()
+                .and_then(source::authorization)
+                .map(Cow::Borrowed),
+            InitDataSource::Header(name) => headers.get(name)?.to_str().ok().map(Cow::Borrowed),
+            InitDataSource::Query(name) => source::form_field(uri?.query()?.as_bytes(), name),
+            InitDataSource::Cookie(name) => source::cookie(
+                headers
+                    .get_all(header::COOKIE)
+                    .iter()
+                    .filter_map(|x| x.to_str().ok()),
+                name,
+            ),
+            InitDataSource::FormField(name) => source::form_field(body?, name),
+            InitDataSource::JsonField(name) => source::json_field(body?, name).map(Cow::Owned),
+        })
+    }
+}
+
+impl fmt::Display for ExtractError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotPresent => f.write_str("init data is not present"),
+            Self::Invalid(e) => write!(f, "invalid init data: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for ExtractError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Invalid(e) => Some(e),
+            Self::NotPresent => None,
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{Error, ThemeParams, Validator, WebAppInitData, WebAppVersion};
+
+/// Launch parameters of a Mini App, passed in the fragment of its URL.
+///
+/// Only the initData is signed. The other parameters are set by the client and must not be
+/// trusted, e.g. use [`WebAppInitData::start_param`] instead of [`LaunchParams::start_param`]
+/// for anything security-sensitive.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LaunchParams {
+    init_data: WebAppInitData,
+    version: Option<WebAppVersion>,
+    platform: Option<Platform>,
+    theme_params: Option<ThemeParams>,
+    start_param: Option<String>,
+    bot_inline: bool,
+    fullscreen: bool,
+}
+
+/// The platform of the Telegram client, from `tgWebAppPlatform`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(from = "String", into = "String")]
+pub enum Platform {
+    Android,
+    Ios,
+    Tdesktop,
+    Macos,
+    Weba,
+    Webk,
+    Unknown(String),
+}
+
+impl LaunchParams {
+    /// Parse a full Mini App URL, its fragment (with or without the leading `#`) or a bare list of
+    /// launch parameters, and validate its `tgWebAppData` with `validator`.
+    ///
+    /// The parameters of a URL are read from both its query string, where Telegram puts
+    /// `tgWebAppStartParam` for direct links, and its fragment, which takes precedence.
+    pub fn parse(url: &str, validator: &Validator) -> Result<Self, Error> {
+        let (query, fragment) = match url.split_once('#') {
+            Some((url, fragment)) => (url.split_once('?').map_or("", |(_, query)| query), fragment),
+            None => match url.split_once('?') {
+                Some((_, query)) => (query, ""),
+                None => ("", url),
+            },
+        };
+
+        let mut init_data = None;
+        let mut version = None;
+        let mut platform = None;
+        let mut theme_params = None;
+        let mut start_param = None;
+        let mut bot_inline = false;
+        let mut fullscreen = false;
+        let params = form_urlencoded::parse(query.as_bytes())
+            .chain(form_urlencoded::parse(fragment.as_bytes()));
+        for (k, v) in params {
+            match &*k {
+                "tgWebAppData" => init_data = Some(v),
+                "tgWebAppVersion" => version = Some(v),
+                "tgWebAppPlatform" => platform = Some(Platform::from(v.into_owned())),
+                "tgWebAppThemeParams" => theme_params = Some(v),
+                "tgWebAppStartParam" => start_param = Some(v.into_owned()),
+                "tgWebAppBotInline" => bot_inline = v == "1",
+                "tgWebAppFullscreen" => fullscreen = v == "1",
+                _ => (),
+            }
+        }
+
+        let init_data = init_data.ok_or(Error::MissingField("tgWebAppData"))?;
+        Ok(LaunchParams {
+            init_data: validator.validate(init_data.as_bytes())?,
+            version: version.and_then(|x| x.parse().ok()),
+            platform,
+            theme_params: theme_params.and_then(|x| serde_json::from_str(&x).ok()),
+            start_param,
+            bot_inline,
+            fullscreen,
+        })
+    }
+
+    /// The validated `tgWebAppData`.
+    pub fn init_data(&self) -> &WebAppInitData {
+        &self.init_data
+    }
+
+    pub fn into_init_data(self) -> WebAppInitData {
+        self.init_data
+    }
+
+    /// The `tgWebAppVersion`, or `None` if it is missing or not in the `major.minor` format.
+    pub fn version(&self) -> Option<WebAppVersion> {
+        self.version
+    }
+
+    pub fn platform(&self) -> Option<&Platform> {
+        self.platform.as_ref()
+    }
+
+    /// The `tgWebAppThemeParams`, or `None` if they are missing or not a JSON object.
+    pub fn theme_params(&self) -> Option<&ThemeParams> {
+        self.theme_params.as_ref()
+    }
+
+    /// The unsigned `tgWebAppStartParam`.
+    pub fn start_param(&self) -> Option<&str> {
+        self.start_param.as_deref()
+    }
+
+    /// Whether the Mini App was launched from inline mode.
+    pub fn bot_inline(&self) -> bool {
+        self.bot_inline
+    }
+
+    /// Whether the Mini App was launched in fullscreen mode.
+    pub fn fullscreen(&self) -> bool {
+        self.fullscreen
+    }
+}
+
+impl Platform {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Android => "android",
+            Self::Ios => "ios",
+            Self::Tdesktop => "tdesktop",
+            Self::Macos => "macos",
+            Self::Weba => "weba",
+            Self::Webk => "webk",
+            Self::Unknown(x) => x,
+        }
+    }
+}
+
+impl From<Platform> for String {
+    fn from(value: Platform) -> Self {
+        match value {
+            Platform::Unknown(x) => x,
+            other => other.as_str().to_owned(),
+        }
+    }
+}
+
+impl From<String> for Platform {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "android" => Self::Android,
+            "ios" => Self::Ios,
+            "tdesktop" => Self::Tdesktop,
+            "macos" => Self::Macos,
+            "weba" => Self::Weba,
+            "webk" => Self::Webk,
+            _ => Self::Unknown(value),
+        }
+    }
+}
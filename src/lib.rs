@@ -1,87 +1,254 @@
 //! Parse and validate initData for Telegram Mini Apps
 
+use std::borrow::Cow;
 use std::collections::BTreeMap;
 use std::time::{Duration, SystemTime};
 
-use serde::Deserialize;
+use serde::de::{DeserializeOwned, Error as _};
+use serde::{Deserialize, Deserializer, Serialize};
 
-pub enum Error {
-    InvalidHash,
-    MissingField(&'static str),
-    InvalidJson(&'static str, serde_json::Error),
-    InvalidNumericField(&'static str),
+#[cfg(feature = "actix-web")]
+pub mod actix;
+#[cfg(feature = "async-graphql")]
+pub mod async_graphql;
+#[cfg(feature = "axum")]
+pub mod axum;
+#[cfg(feature = "signing")]
+mod builder;
+mod contact;
+mod error;
+#[cfg(feature = "http")]
+pub mod http;
+mod launch_params;
+mod login_widget;
+mod options;
+#[cfg(feature = "rocket")]
+pub mod rocket;
+mod signed_form;
+#[cfg(any(feature = "http", feature = "actix-web"))]
+mod source;
+mod start_param;
+mod theme;
+#[cfg(feature = "tonic")]
+pub mod tonic;
+#[cfg(feature = "tower")]
+pub mod tower;
+mod validator;
+mod version;
+
+#[cfg(feature = "signing")]
+pub use builder::{WebAppInitDataBuilder, ed25519_public_key};
+pub use contact::SharedContact;
+pub use error::Error;
+pub use launch_params::{LaunchParams, Platform};
+pub use login_widget::LoginWidgetData;
+pub use options::{Clock, SystemClock, ValidationOptions};
+pub use signed_form::SignedForm;
+pub use start_param::{
+    StartParamError, StartParamSigner, check_start_param, decode_start_param, encode_start_param,
+};
+pub use theme::{ColorScheme, ParseRgbError, Rgb, ThemeParams};
+pub use validator::Validator;
+pub use version::{Capability, ParseVersionError, WebAppVersion};
+
+/// The default maximum accepted length of raw initData, in bytes.
+pub const MAX_INIT_DATA_LEN: usize = 16 * 1024;
+
+/// The maximum length of a `start_param` accepted by Telegram.
+pub const MAX_START_PARAM_LEN: usize = 512;
+
+/// The initData fields accepted in strict mode.
+pub const KNOWN_FIELDS: &[&str] = &[
+    "query_id",
+    "user",
+    "receiver",
+    "chat",
+    "chat_type",
+    "chat_instance",
+    "start_param",
+    "can_send_after",
+    "auth_date",
+    "hash",
+    "signature",
+];
+
+/// Telegram environment whose Ed25519 key signs the `signature` field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Environment {
+    Production,
+    Test,
+}
+
+impl Environment {
+    pub fn public_key(self) -> [u8; 32] {
+        match self {
+            Self::Production => [
+                0xe7, 0xbf, 0x03, 0xa2, 0xfa, 0x46, 0x02, 0xaf, 0x45, 0x80, 0x70, 0x3d, 0x88, 0xdd,
+                0xa5, 0xbb, 0x59, 0xf3, 0x2e, 0xd8, 0xb0, 0x2a, 0x56, 0xc1, 0x87, 0xfe, 0x7d, 0x34,
+                0xca, 0xed, 0x24, 0x2d,
+            ],
+            Self::Test => [
+                0x40, 0x05, 0x50, 0x58, 0xa4, 0xee, 0x38, 0x15, 0x6a, 0x06, 0x56, 0x2e, 0x52, 0xee,
+                0xce, 0x92, 0xa7, 0x71, 0xbc, 0xd8, 0x34, 0x6a, 0x8c, 0x46, 0x15, 0xcb, 0x73, 0x76,
+                0xed, 0xdf, 0x72, 0xec,
+            ],
+        }
+    }
 }
 
-#[derive(Debug)]
+/// Validated initData.
+///
+/// (De)serializes to the same JSON shape as `Telegram.WebApp.initDataUnsafe`, without the `hash`
+/// and `signature` fields. Like there, `auth_date` and `can_send_after` may be deserialized from
+/// strings, but they are serialized as numbers.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
 pub struct WebAppInitData {
-    // query_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    query_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     user: Option<WebAppUser>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     receiver: Option<WebAppUser>,
-    // chat: Option<String>,
-    // chat_type: Option<String>,
-    // chat_instance: Option<String>,
-    // start_param: Option<String>,
-    // can_send_after: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    chat: Option<WebAppChat>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    chat_type: Option<ChatType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    chat_instance: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    start_param: Option<String>,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_optional_number",
+        skip_serializing_if = "Option::is_none"
+    )]
+    can_send_after: Option<u64>,
+    #[serde(deserialize_with = "deserialize_unix_time")]
     auth_date: u64,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
 pub struct WebAppUser {
     id: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
     is_bot: Option<bool>,
     first_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
     last_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     username: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     language_code: Option<String>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "is_false")]
     is_premium: bool,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "is_false")]
     added_to_attachment_menu: bool,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "is_false")]
     allows_write_to_pm: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    photo_url: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub struct WebAppChat {
+    id: i64,
+    #[serde(rename = "type")]
+    chat_type: ChatType,
+    title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    username: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     photo_url: Option<String>,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(from = "String", into = "String")]
+pub enum ChatType {
+    Sender,
+    Private,
+    Group,
+    Supergroup,
+    Channel,
+    Unknown(String),
+}
+
 impl WebAppInitData {
+    /// Validate initData using the bot token.
+    ///
+    /// Prefer a [`Validator`] if you validate initData repeatedly.
     pub fn new(token: &str, raw: &[u8]) -> Result<Self, Error> {
-        let mut decoded: BTreeMap<_, _> = form_urlencoded::parse(raw).collect();
-        let hash = decoded.remove("hash").ok_or(Error::MissingField("hash"))?;
-
-        let mut data_check_string = String::new();
-        for (k, v) in &decoded {
-            if !data_check_string.is_empty() {
-                data_check_string.push('\n');
-            }
-            data_check_string.push_str(k);
-            data_check_string.push('=');
-            data_check_string.push_str(v);
-        }
+        Validator::new(token).validate(raw)
+    }
 
-        let secret_key = hmac_sha256::HMAC::mac(token, "WebAppData");
-        let actual_hash = hmac_sha256::HMAC::mac(&data_check_string, secret_key);
-        if hex(&actual_hash) != *hash {
-            return Err(Error::InvalidHash);
-        }
+    pub fn new_with_options(
+        token: &str,
+        raw: &[u8],
+        options: &ValidationOptions,
+    ) -> Result<Self, Error> {
+        Validator::new(token)
+            .with_options(options.clone())
+            .validate(raw)
+    }
+
+    /// Validate initData using the `signature` field, without knowing the bot token.
+    ///
+    /// This is meant for third parties, which only know the id of the bot.
+    pub fn new_third_party(bot_id: u64, env: Environment, raw: &[u8]) -> Result<Self, Error> {
+        Validator::third_party(bot_id, env).validate(raw)
+    }
+
+    pub fn new_third_party_with_options(
+        bot_id: u64,
+        env: Environment,
+        raw: &[u8],
+        options: &ValidationOptions,
+    ) -> Result<Self, Error> {
+        Validator::third_party(bot_id, env)
+            .with_options(options.clone())
+            .validate(raw)
+    }
 
+    fn from_fields(mut fields: BTreeMap<Cow<str>, Cow<str>>) -> Result<Self, Error> {
         Ok(WebAppInitData {
-            user: decoded
+            query_id: fields.remove("query_id").map(Cow::into_owned),
+            user: fields
                 .remove("user")
                 .map(|x| serde_json::from_str(&x))
                 .transpose()
                 .map_err(|e| Error::InvalidJson("user", e))?,
-            receiver: decoded
+            receiver: fields
                 .remove("receiver")
                 .map(|x| serde_json::from_str(&x))
                 .transpose()
                 .map_err(|e| Error::InvalidJson("receiver", e))?,
-            auth_date: decoded
-                .remove("auth_date")
-                .ok_or(Error::MissingField("auth_date"))?
-                .parse()
-                .map_err(|_e| Error::InvalidNumericField("auth_date"))?,
+            chat: fields
+                .remove("chat")
+                .map(|x| serde_json::from_str(&x))
+                .transpose()
+                .map_err(|e| Error::InvalidJson("chat", e))?,
+            chat_type: fields
+                .remove("chat_type")
+                .map(|x| ChatType::from(x.into_owned())),
+            chat_instance: fields.remove("chat_instance").map(Cow::into_owned),
+            start_param: fields.remove("start_param").map(Cow::into_owned),
+            can_send_after: fields
+                .remove("can_send_after")
+                .map(|x| x.parse())
+                .transpose()
+                .map_err(|_e| Error::InvalidNumericField("can_send_after"))?,
+            auth_date: parse_unix_time(
+                "auth_date",
+                &fields
+                    .remove("auth_date")
+                    .ok_or(Error::MissingField("auth_date"))?,
+            )?,
         })
     }
 
+    pub fn query_id(&self) -> Option<&str> {
+        self.query_id.as_deref()
+    }
+
     pub fn user(&self) -> Option<&WebAppUser> {
         self.user.as_ref()
     }
@@ -90,13 +257,68 @@ impl WebAppInitData {
         self.receiver.as_ref()
     }
 
+    pub fn chat(&self) -> Option<&WebAppChat> {
+        self.chat.as_ref()
+    }
+
+    pub fn chat_type(&self) -> Option<&ChatType> {
+        self.chat_type.as_ref()
+    }
+
+    pub fn chat_instance(&self) -> Option<&str> {
+        self.chat_instance.as_deref()
+    }
+
+    pub fn start_param(&self) -> Option<&str> {
+        self.start_param.as_deref()
+    }
+
+    /// Decode a `start_param` made by [`encode_start_param`].
+    pub fn decode_start_param<T: DeserializeOwned>(&self) -> Result<Option<T>, StartParamError> {
+        self.start_param().map(decode_start_param).transpose()
+    }
+
+    /// Verify a `start_param` signed with `signer` and decode its payload.
+    pub fn verify_start_param<T: DeserializeOwned>(
+        &self,
+        signer: &StartParamSigner,
+    ) -> Result<Option<T>, StartParamError> {
+        self.start_param().map(|x| signer.verify(x)).transpose()
+    }
+
+    pub fn can_send_after(&self) -> Option<Duration> {
+        self.can_send_after.map(Duration::from_secs)
+    }
+
+    pub fn auth_date(&self) -> SystemTime {
+        unix_time(self.auth_date).expect("auth_date is checked when parsing")
+    }
+
+    /// The moment after which a message can be sent via `answerWebAppQuery`.
+    ///
+    /// Returns `None` if there is no `query_id`, i.e. the method cannot be used at all, or if the
+    /// moment is too far in the future to be represented.
+    pub fn can_send_at(&self) -> Option<SystemTime> {
+        self.query_id.as_ref()?;
+        self.auth_date()
+            .checked_add(self.can_send_after().unwrap_or_default())
+    }
+
+    /// Whether a message can be sent via `answerWebAppQuery` right now.
+    pub fn can_send_now(&self) -> bool {
+        self.can_send_now_at(&SystemClock)
+    }
+
+    pub fn can_send_now_at(&self, clock: &dyn Clock) -> bool {
+        self.can_send_at().is_some_and(|at| at <= clock.now())
+    }
+
     pub fn elapsed_since_auth(&self) -> Option<Duration> {
-        let now = SystemTime::now()
-            .duration_since(SystemTime::UNIX_EPOCH)
-            .ok()?
-            .as_secs();
-        let secs = now.checked_sub(self.auth_date)?;
-        Some(Duration::from_secs(secs))
+        self.elapsed_since_auth_at(&SystemClock)
+    }
+
+    pub fn elapsed_since_auth_at(&self, clock: &dyn Clock) -> Option<Duration> {
+        clock.now().duration_since(self.auth_date()).ok()
     }
 }
 
@@ -142,11 +364,110 @@ impl WebAppUser {
     }
 }
 
-fn hex(bytes: &[u8]) -> String {
-    let mut result = String::with_capacity(bytes.len() * 2);
-    for byte in bytes {
-        use std::fmt::Write;
-        let _ = write!(result, "{byte:02x}");
+impl WebAppChat {
+    pub fn id(&self) -> i64 {
+        self.id
+    }
+
+    pub fn chat_type(&self) -> &ChatType {
+        &self.chat_type
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn username(&self) -> Option<&str> {
+        self.username.as_deref()
+    }
+
+    pub fn photo_url(&self) -> Option<&str> {
+        self.photo_url.as_deref()
+    }
+}
+
+impl ChatType {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Sender => "sender",
+            Self::Private => "private",
+            Self::Group => "group",
+            Self::Supergroup => "supergroup",
+            Self::Channel => "channel",
+            Self::Unknown(x) => x,
+        }
+    }
+}
+
+impl From<ChatType> for String {
+    fn from(value: ChatType) -> Self {
+        match value {
+            ChatType::Unknown(x) => x,
+            other => other.as_str().to_owned(),
+        }
+    }
+}
+
+impl From<String> for ChatType {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "sender" => Self::Sender,
+            "private" => Self::Private,
+            "group" => Self::Group,
+            "supergroup" => Self::Supergroup,
+            "channel" => Self::Channel,
+            _ => Self::Unknown(value),
+        }
+    }
+}
+
+fn is_false(x: &bool) -> bool {
+    !x
+}
+
+/// Convert Unix seconds to a [`SystemTime`], if it can be represented.
+pub(crate) fn unix_time(secs: u64) -> Option<SystemTime> {
+    SystemTime::UNIX_EPOCH.checked_add(Duration::from_secs(secs))
+}
+
+/// Parse the Unix timestamp `field`, rejecting the values not representable as a [`SystemTime`].
+pub(crate) fn parse_unix_time(field: &'static str, value: &str) -> Result<u64, Error> {
+    value
+        .parse()
+        .ok()
+        .filter(|x| unix_time(*x).is_some())
+        .ok_or(Error::InvalidNumericField(field))
+}
+
+/// A number, possibly as a string.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum NumberOrString {
+    Number(u64),
+    String(String),
+}
+
+impl NumberOrString {
+    fn into_number<E: serde::de::Error>(self) -> Result<u64, E> {
+        match self {
+            Self::Number(x) => Ok(x),
+            Self::String(x) => x.parse().map_err(E::custom),
+        }
+    }
+}
+
+fn deserialize_optional_number<'de, D: Deserializer<'de>>(d: D) -> Result<Option<u64>, D::Error> {
+    Option::<NumberOrString>::deserialize(d)?
+        .map(NumberOrString::into_number)
+        .transpose()
+}
+
+/// Deserialize a Unix timestamp, possibly as a string, rejecting the values not representable as
+/// a [`SystemTime`].
+pub(crate) fn deserialize_unix_time<'de, D: Deserializer<'de>>(d: D) -> Result<u64, D::Error> {
+    let secs = NumberOrString::deserialize(d)?.into_number()?;
+    match unix_time(secs) {
+        Some(_) => Ok(secs),
+        None => Err(D::Error::custom("timestamp is out of range")),
     }
-    result
 }
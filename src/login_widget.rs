@@ -0,0 +1,116 @@
+use std::borrow::Cow;
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Error, SignedForm, ValidationOptions, WebAppUser, deserialize_unix_time, parse_unix_time,
+    unix_time,
+};
+
+const KNOWN_FIELDS: &[&str] = &[
+    "id",
+    "first_name",
+    "last_name",
+    "username",
+    "photo_url",
+    "auth_date",
+    "hash",
+];
+
+/// Validated data from the Telegram Login Widget or a `login_url` button.
+///
+/// It is signed the same way as initData, but with `SHA256(token)` as the secret key.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub struct LoginWidgetData {
+    id: i64,
+    first_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    username: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    photo_url: Option<String>,
+    #[serde(deserialize_with = "deserialize_unix_time")]
+    auth_date: u64,
+}
+
+impl LoginWidgetData {
+    pub fn new(token: &str, raw: &[u8]) -> Result<Self, Error> {
+        Self::new_with_options(token, raw, &ValidationOptions::default())
+    }
+
+    pub fn new_with_options(
+        token: &str,
+        raw: &[u8],
+        options: &ValidationOptions,
+    ) -> Result<Self, Error> {
+        let form = SignedForm::parse_known(raw, options, Some(KNOWN_FIELDS))?;
+        form.verify(&SignedForm::login_widget_secret_key(token))?;
+        let mut fields = form.into_fields();
+        options.check_required(&fields)?;
+
+        let data = LoginWidgetData {
+            id: fields
+                .remove("id")
+                .ok_or(Error::MissingField("id"))?
+                .parse()
+                .map_err(|_e| Error::InvalidNumericField("id"))?,
+            first_name: fields
+                .remove("first_name")
+                .ok_or(Error::MissingField("first_name"))?
+                .into_owned(),
+            last_name: fields.remove("last_name").map(Cow::into_owned),
+            username: fields.remove("username").map(Cow::into_owned),
+            photo_url: fields.remove("photo_url").map(Cow::into_owned),
+            auth_date: parse_unix_time(
+                "auth_date",
+                &fields
+                    .remove("auth_date")
+                    .ok_or(Error::MissingField("auth_date"))?,
+            )?,
+        };
+        options.check_freshness(data.auth_date)?;
+        Ok(data)
+    }
+
+    pub fn id(&self) -> i64 {
+        self.id
+    }
+
+    pub fn first_name(&self) -> &str {
+        &self.first_name
+    }
+
+    pub fn last_name(&self) -> Option<&str> {
+        self.last_name.as_deref()
+    }
+
+    pub fn username(&self) -> Option<&str> {
+        self.username.as_deref()
+    }
+
+    pub fn photo_url(&self) -> Option<&str> {
+        self.photo_url.as_deref()
+    }
+
+    pub fn auth_date(&self) -> SystemTime {
+        unix_time(self.auth_date).expect("auth_date is checked when parsing")
+    }
+
+    /// The user as a [`WebAppUser`], with the fields unknown to the widget left empty.
+    pub fn to_user(&self) -> WebAppUser {
+        WebAppUser {
+            id: self.id,
+            is_bot: None,
+            first_name: self.first_name.clone(),
+            last_name: self.last_name.clone(),
+            username: self.username.clone(),
+            language_code: None,
+            is_premium: false,
+            added_to_attachment_menu: false,
+            allows_write_to_pm: false,
+            photo_url: self.photo_url.clone(),
+        }
+    }
+}
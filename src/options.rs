@@ -0,0 +1,162 @@
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+#[cfg(doc)]
+use crate::KNOWN_FIELDS;
+use crate::{Error, MAX_INIT_DATA_LEN, unix_time};
+
+/// A source of the current time.
+///
+/// Use a custom implementation to validate initData against a fixed point in time, e.g. in tests.
+pub trait Clock: Send + Sync {
+    fn now(&self) -> SystemTime;
+}
+
+/// The real clock, backed by [`SystemTime::now`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+impl Clock for SystemTime {
+    fn now(&self) -> SystemTime {
+        *self
+    }
+}
+
+/// Options controlling the validation of initData.
+///
+/// By default `auth_date` is not checked at all, no fields except `auth_date` are required, the
+/// input is limited to [`MAX_INIT_DATA_LEN`] bytes and strict mode is enabled.
+#[derive(Clone)]
+pub struct ValidationOptions {
+    max_age: Option<Duration>,
+    max_future_skew: Option<Duration>,
+    clock: Arc<dyn Clock>,
+    pub(crate) max_len: usize,
+    required: Vec<&'static str>,
+    pub(crate) strict: bool,
+    pub(crate) allowed: Vec<&'static str>,
+}
+
+impl Default for ValidationOptions {
+    fn default() -> Self {
+        Self {
+            max_age: None,
+            max_future_skew: None,
+            clock: Arc::new(SystemClock),
+            max_len: MAX_INIT_DATA_LEN,
+            required: Vec::new(),
+            strict: true,
+            allowed: Vec::new(),
+        }
+    }
+}
+
+impl fmt::Debug for ValidationOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ValidationOptions")
+            .field("max_age", &self.max_age)
+            .field("max_future_skew", &self.max_future_skew)
+            .field("max_len", &self.max_len)
+            .field("required", &self.required)
+            .field("strict", &self.strict)
+            .field("allowed", &self.allowed)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ValidationOptions {
+    /// Reject initData with `auth_date` older than `max_age`.
+    ///
+    /// Unless [`max_future_skew`](Self::max_future_skew) is set as well, `auth_date` in the future
+    /// is rejected too, so that the data is valid for at most `max_age`.
+    pub fn max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Reject initData with `auth_date` more than `skew` in the future.
+    pub fn max_future_skew(mut self, skew: Duration) -> Self {
+        self.max_future_skew = Some(skew);
+        self
+    }
+
+    /// Reject initData longer than `max_len` bytes.
+    pub fn max_len(mut self, max_len: usize) -> Self {
+        self.max_len = max_len;
+        self
+    }
+
+    /// Reject initData without the given field, e.g. `"user"` or `"query_id"`.
+    pub fn require(mut self, field: &'static str) -> Self {
+        self.required.push(field);
+        self
+    }
+
+    /// Enable or disable strict mode.
+    ///
+    /// In strict mode duplicate keys, empty keys and unknown keys are rejected. Known keys are the
+    /// fields of the validated payload, e.g. [`KNOWN_FIELDS`] for initData, and those added with
+    /// [`allow`](Self::allow). Otherwise the last of the duplicate values is used and other keys
+    /// are accepted.
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Accept `field` in strict mode, in addition to the known fields.
+    pub fn allow(mut self, field: &'static str) -> Self {
+        self.allowed.push(field);
+        self
+    }
+
+    pub fn clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    pub fn now(&self) -> SystemTime {
+        self.clock.now()
+    }
+
+    pub(crate) fn is_allowed(&self, known: &[&str], field: &str) -> bool {
+        known.contains(&field) || self.allowed.contains(&field)
+    }
+
+    pub(crate) fn check_required(
+        &self,
+        fields: &BTreeMap<Cow<str>, Cow<str>>,
+    ) -> Result<(), Error> {
+        match self.required.iter().find(|x| !fields.contains_key(**x)) {
+            Some(field) => Err(Error::MissingField(field)),
+            None => Ok(()),
+        }
+    }
+
+    /// Check `auth_date`, in Unix seconds, against `max_age` and `max_future_skew`.
+    pub(crate) fn check_freshness(&self, auth_date: u64) -> Result<(), Error> {
+        if self.max_age.is_none() && self.max_future_skew.is_none() {
+            return Ok(());
+        }
+        let auth_date = unix_time(auth_date).expect("auth_date is checked when parsing");
+        let now = self.now();
+        match now.duration_since(auth_date) {
+            Ok(age) => match self.max_age {
+                Some(max_age) if age > max_age => Err(Error::Expired),
+                _ => Ok(()),
+            },
+            Err(e) => match self.max_future_skew.unwrap_or(Duration::ZERO) {
+                skew if e.duration() > skew => Err(Error::AuthDateInFuture),
+                _ => Ok(()),
+            },
+        }
+    }
+}
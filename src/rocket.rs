@@ -0,0 +1,92 @@
+//! Integration with `rocket`.
+//!
+//! [`WebAppInitData`] and [`WebAppUser`] are request guards. The initData is read from the
+//! `Authorization: tma <initData>` header and validated with the [`Validator`] from the managed
+//! state.
+//!
+//! A request without initData is forwarded with `401 Unauthorized`, so that another route may
+//! handle it. Invalid initData fails with `401 Unauthorized` or `400 Bad Request`.
+
+use std::fmt;
+
+use rocket::http::Status;
+use rocket::request::{FromRequest, Outcome, Request};
+
+use crate::{Error, Validator, WebAppInitData, WebAppUser};
+
+/// The error of the request guards.
+#[derive(Debug)]
+pub enum TelegramAuthRejection {
+    /// No [`Validator`] is managed by Rocket.
+    MissingValidator,
+    /// The initData is invalid.
+    Invalid(Error),
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for WebAppInitData {
+    type Error = TelegramAuthRejection;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let Some(validator) = request.rocket().state::<Validator>() else {
+            return Outcome::Error((
+                Status::InternalServerError,
+                TelegramAuthRejection::MissingValidator,
+            ));
+        };
+        let Some(init_data) = request
+            .headers()
+            .get_one("Authorization")
+            .and_then(|x| x.strip_prefix("tma "))
+        else {
+            return Outcome::Forward(Status::Unauthorized);
+        };
+        match validator.validate(init_data.as_bytes()) {
+            Ok(data) => Outcome::Success(data),
+            Err(e) => {
+                let status = if e.is_unauthorized() {
+                    Status::Unauthorized
+                } else {
+                    Status::BadRequest
+                };
+                Outcome::Error((status, TelegramAuthRejection::Invalid(e)))
+            }
+        }
+    }
+}
+
+/// Requires the initData to contain the `user` field.
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for WebAppUser {
+    type Error = TelegramAuthRejection;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        WebAppInitData::from_request(request)
+            .await
+            .and_then(|data| match data.user {
+                Some(user) => Outcome::Success(user),
+                None => Outcome::Error((
+                    Status::BadRequest,
+                    TelegramAuthRejection::Invalid(Error::MissingField("user")),
+                )),
+            })
+    }
+}
+
+impl fmt::Display for TelegramAuthRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingValidator => f.write_str("validator is not managed"),
+            Self::Invalid(e) => write!(f, "invalid init data: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for TelegramAuthRejection {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Invalid(e) => Some(e),
+            Self::MissingValidator => None,
+        }
+    }
+}
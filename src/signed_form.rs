@@ -0,0 +1,186 @@
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+
+use crate::{Error, ValidationOptions};
+
+/// A raw form signed by Telegram, such as initData.
+///
+/// This is the low-level building block of the typed payloads in this crate. It gives access to
+/// all of the fields, including the ones not modeled by this crate, and to the canonical
+/// data-check-string: the fields except `hash`, sorted by key and formatted as `key=value` lines.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SignedForm<'a> {
+    fields: BTreeMap<Cow<'a, str>, Cow<'a, str>>,
+}
+
+impl<'a> SignedForm<'a> {
+    /// Parse a URL-encoded form, with the default options.
+    pub fn parse(raw: &'a [u8]) -> Result<Self, Error> {
+        Self::parse_with_options(raw, &ValidationOptions::default())
+    }
+
+    /// Parse a URL-encoded form.
+    ///
+    /// Only the size limit and strict mode of `options` are used. Since the fields of the form are
+    /// not known, strict mode only rejects duplicate and empty keys.
+    pub fn parse_with_options(raw: &'a [u8], options: &ValidationOptions) -> Result<Self, Error> {
+        Self::parse_known(raw, options, None)
+    }
+
+    /// Like [`parse_with_options`](Self::parse_with_options), but in strict mode also rejects
+    /// keys other than `known` and those allowed in `options`.
+    pub(crate) fn parse_known(
+        raw: &'a [u8],
+        options: &ValidationOptions,
+        known: Option<&[&str]>,
+    ) -> Result<Self, Error> {
+        if raw.len() > options.max_len {
+            return Err(Error::TooLarge {
+                len: raw.len(),
+                max: options.max_len,
+            });
+        }
+        let mut fields = BTreeMap::new();
+        for (k, v) in form_urlencoded::parse(raw) {
+            if options.strict {
+                if k.is_empty() {
+                    return Err(Error::EmptyKey);
+                }
+                if known.is_some_and(|known| !options.is_allowed(known, &k)) {
+                    return Err(Error::UnknownKey(k.into_owned()));
+                }
+                if fields.contains_key(&k) {
+                    return Err(Error::DuplicateKey(k.into_owned()));
+                }
+            }
+            fields.insert(k, v);
+        }
+        Ok(Self { fields })
+    }
+
+    /// The secret key for initData and `requestContact` responses: `HMAC("WebAppData", token)`.
+    pub fn web_app_secret_key(token: &str) -> [u8; 32] {
+        hmac_sha256::HMAC::mac(token, "WebAppData")
+    }
+
+    /// The secret key for Login Widget data: `SHA256(token)`.
+    pub fn login_widget_secret_key(token: &str) -> [u8; 32] {
+        hmac_sha256::Hash::hash(token.as_bytes())
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.fields.get(key).map(|x| &**x)
+    }
+
+    /// All of the fields, sorted by key.
+    pub fn fields(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.fields.iter().map(|(k, v)| (&**k, &**v))
+    }
+
+    pub fn hash(&self) -> Option<&str> {
+        self.get("hash")
+    }
+
+    pub fn signature(&self) -> Option<&str> {
+        self.get("signature")
+    }
+
+    /// The data-check-string used to verify the `hash` field.
+    pub fn data_check_string(&self) -> String {
+        let mut result = String::new();
+        write_data_check_string(&self.fields, &["hash"], |x| result.push_str(x));
+        result
+    }
+
+    /// Check that the `hash` field is the HMAC-SHA256 of the data-check-string under `secret_key`.
+    pub fn verify(&self, secret_key: &[u8; 32]) -> Result<(), Error> {
+        let hash = self.hash().ok_or(Error::MissingField("hash"))?;
+        let hash = decode_hash(hash).ok_or(Error::MalformedHash)?;
+        let mut hmac = hmac_sha256::HMAC::new(secret_key);
+        write_data_check_string(&self.fields, &["hash"], |x| hmac.update(x));
+        if !ct_eq(&hmac.finalize(), &hash) {
+            return Err(Error::InvalidHash);
+        }
+        Ok(())
+    }
+
+    /// Check the Ed25519 `signature` field of initData, made for the bot `bot_id` with the key
+    /// corresponding to `public_key`.
+    ///
+    /// The signed message is `<bot_id>:WebAppData\n` followed by the data-check-string without
+    /// the `signature` field.
+    pub fn verify_signature(&self, bot_id: u64, public_key: &[u8; 32]) -> Result<(), Error> {
+        let signature = self.signature().ok_or(Error::MissingField("signature"))?;
+        let signature = URL_SAFE_NO_PAD
+            .decode(signature.as_bytes())
+            .ok()
+            .and_then(|x| ed25519_compact::Signature::from_slice(&x).ok())
+            .ok_or(Error::MalformedSignature)?;
+        let mut state = ed25519_compact::PublicKey::new(*public_key)
+            .verify_incremental(&signature)
+            .map_err(|_e| Error::InvalidSignature)?;
+        state.absorb(bot_id.to_string());
+        state.absorb(":WebAppData\n");
+        write_data_check_string(&self.fields, &["hash", "signature"], |x| state.absorb(x));
+        state.verify().map_err(|_e| Error::InvalidSignature)
+    }
+
+    pub(crate) fn into_fields(self) -> BTreeMap<Cow<'a, str>, Cow<'a, str>> {
+        self.fields
+    }
+}
+
+/// Feed the data-check-string, i.e. sorted `key=value` pairs separated by `\n`, to `sink`,
+/// skipping the `excluded` keys.
+pub(crate) fn write_data_check_string<K, V>(
+    fields: &BTreeMap<K, V>,
+    excluded: &[&str],
+    mut sink: impl FnMut(&str),
+) where
+    K: AsRef<str>,
+    V: AsRef<str>,
+{
+    let mut first = true;
+    for (k, v) in fields {
+        if excluded.contains(&k.as_ref()) {
+            continue;
+        }
+        if !first {
+            sink("\n");
+        }
+        first = false;
+        sink(k.as_ref());
+        sink("=");
+        sink(v.as_ref());
+    }
+}
+
+fn decode_hash(hex: &str) -> Option<[u8; 32]> {
+    fn nibble(x: u8) -> Option<u8> {
+        match x {
+            b'0'..=b'9' => Some(x - b'0'),
+            b'a'..=b'f' => Some(x - b'a' + 10),
+            b'A'..=b'F' => Some(x - b'A' + 10),
+            _ => None,
+        }
+    }
+
+    let hex: &[u8; 64] = hex.as_bytes().try_into().ok()?;
+    let mut result = [0; 32];
+    for (byte, pair) in result.iter_mut().zip(hex.chunks_exact(2)) {
+        *byte = (nibble(pair[0])? << 4) | nibble(pair[1])?;
+    }
+    Some(result)
+}
+
+/// Compare two hashes in constant time.
+pub(crate) fn ct_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let diff = a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y));
+    std::hint::black_box(diff) == 0
+}
@@ -0,0 +1,35 @@
+//! Locating initData in the parts of a request, shared by the integrations with different
+//! versions of the `http` types.
+
+use std::borrow::Cow;
+
+/// The initData from the value of an `Authorization: tma <initData>` header.
+pub(crate) fn authorization(value: &str) -> Option<&str> {
+    value.strip_prefix("tma ")
+}
+
+/// A field of an `application/x-www-form-urlencoded` string, e.g. a query string.
+pub(crate) fn form_field<'a>(form: &'a [u8], name: &str) -> Option<Cow<'a, str>> {
+    form_urlencoded::parse(form)
+        .find(|(k, _)| k == name)
+        .map(|(_, v)| v)
+}
+
+/// A percent-encoded cookie from the values of the `Cookie` headers.
+pub(crate) fn cookie<'a>(
+    headers: impl IntoIterator<Item = &'a str>,
+    name: &str,
+) -> Option<Cow<'a, str>> {
+    headers
+        .into_iter()
+        .flat_map(|x| x.split(';'))
+        .filter_map(|x| x.trim().split_once('='))
+        .find(|(k, _)| *k == name)
+        .and_then(|(_, v)| percent_encoding::percent_decode_str(v).decode_utf8().ok())
+}
+
+/// A string field of a JSON object.
+pub(crate) fn json_field(body: &[u8], name: &str) -> Option<String> {
+    let json: serde_json::Value = serde_json::from_slice(body).ok()?;
+    Some(json.get(name)?.as_str()?.to_owned())
+}
@@ -0,0 +1,185 @@
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::signed_form::ct_eq;
+use crate::{Clock, MAX_START_PARAM_LEN, SystemClock};
+
+/// The length of the truncated HMAC in a signed `start_param`, in bytes.
+const MAC_LEN: usize = 12;
+
+#[derive(Debug)]
+pub enum StartParamError {
+    TooLong {
+        len: usize,
+        max: usize,
+    },
+    /// A character outside of `[A-Za-z0-9_-]`, at the given byte offset.
+    InvalidCharacter {
+        ch: char,
+        position: usize,
+    },
+    /// Not valid base64url.
+    InvalidEncoding,
+    InvalidJson(serde_json::Error),
+    /// Too short to be a signed `start_param`.
+    Malformed,
+    /// The MAC of a signed `start_param` does not match, i.e. it was not made with this secret.
+    Forged,
+    /// The expiry of a signed `start_param` has passed.
+    Expired,
+}
+
+/// Mints and verifies tamper-proof `start_param` values under a server secret.
+///
+/// A signed `start_param` is the base64url encoding of the expiry as big-endian Unix seconds, the
+/// JSON payload and the first 12 bytes of their HMAC-SHA256. Unlike initData, it is not signed by
+/// Telegram, so verify it in addition to validating the initData that carries it.
+#[derive(Clone)]
+pub struct StartParamSigner {
+    key: [u8; 32],
+    clock: Arc<dyn Clock>,
+}
+
+/// Encode `value` as a `start_param`: its JSON, base64url encoded without padding.
+pub fn encode_start_param<T: Serialize + ?Sized>(value: &T) -> Result<String, StartParamError> {
+    let json = serde_json::to_vec(value).map_err(StartParamError::InvalidJson)?;
+    let param = URL_SAFE_NO_PAD.encode(json);
+    check_start_param(&param)?;
+    Ok(param)
+}
+
+/// Decode a `start_param` made by [`encode_start_param`].
+pub fn decode_start_param<T: DeserializeOwned>(param: &str) -> Result<T, StartParamError> {
+    check_start_param(param)?;
+    let json = URL_SAFE_NO_PAD
+        .decode(param)
+        .map_err(|_e| StartParamError::InvalidEncoding)?;
+    serde_json::from_slice(&json).map_err(StartParamError::InvalidJson)
+}
+
+/// Check that `param` is accepted by Telegram as a `start_param`: at most
+/// [`MAX_START_PARAM_LEN`] characters of `[A-Za-z0-9_-]`.
+pub fn check_start_param(param: &str) -> Result<(), StartParamError> {
+    if let Some((position, ch)) = param
+        .char_indices()
+        .find(|(_, x)| !x.is_ascii_alphanumeric() && !matches!(x, '_' | '-'))
+    {
+        return Err(StartParamError::InvalidCharacter { ch, position });
+    }
+    if param.len() > MAX_START_PARAM_LEN {
+        return Err(StartParamError::TooLong {
+            len: param.len(),
+            max: MAX_START_PARAM_LEN,
+        });
+    }
+    Ok(())
+}
+
+impl fmt::Debug for StartParamSigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StartParamSigner").finish_non_exhaustive()
+    }
+}
+
+impl StartParamSigner {
+    pub fn new(secret: &[u8]) -> Self {
+        Self {
+            key: hmac_sha256::HMAC::mac(secret, "StartParam"),
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Use `clock` instead of the system clock to compute and check the expiry.
+    pub fn clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    /// Sign `value` into a `start_param` valid for `ttl`.
+    ///
+    /// A `ttl` too large to be represented makes the value never expire.
+    pub fn sign<T: Serialize + ?Sized>(
+        &self,
+        value: &T,
+        ttl: Duration,
+    ) -> Result<String, StartParamError> {
+        let expires_at = self
+            .clock
+            .now()
+            .checked_add(ttl)
+            .map_or(u64::MAX, unix_secs);
+        let mut data = expires_at.to_be_bytes().to_vec();
+        serde_json::to_writer(&mut data, value).map_err(StartParamError::InvalidJson)?;
+        let mac = hmac_sha256::HMAC::mac(&data, self.key);
+        data.extend_from_slice(&mac[..MAC_LEN]);
+
+        let param = URL_SAFE_NO_PAD.encode(data);
+        check_start_param(&param)?;
+        Ok(param)
+    }
+
+    /// Verify a `start_param` made by [`sign`](Self::sign) and decode its payload.
+    pub fn verify<T: DeserializeOwned>(&self, param: &str) -> Result<T, StartParamError> {
+        check_start_param(param)?;
+        let data = URL_SAFE_NO_PAD
+            .decode(param)
+            .map_err(|_e| StartParamError::InvalidEncoding)?;
+        let (data, mac) = data
+            .len()
+            .checked_sub(MAC_LEN)
+            .filter(|x| *x >= 8)
+            .map(|x| data.split_at(x))
+            .ok_or(StartParamError::Malformed)?;
+        if !ct_eq(&hmac_sha256::HMAC::mac(data, self.key)[..MAC_LEN], mac) {
+            return Err(StartParamError::Forged);
+        }
+
+        let (expires_at, payload) = data.split_at(8);
+        let expires_at = u64::from_be_bytes(expires_at.try_into().unwrap());
+        if unix_secs(self.clock.now()) > expires_at {
+            return Err(StartParamError::Expired);
+        }
+        serde_json::from_slice(payload).map_err(StartParamError::InvalidJson)
+    }
+}
+
+fn unix_secs(time: SystemTime) -> u64 {
+    time.duration_since(SystemTime::UNIX_EPOCH)
+        .map_or(0, |x| x.as_secs())
+}
+
+impl fmt::Display for StartParamError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooLong { len, max } => {
+                write!(
+                    f,
+                    "start_param is too long ({len} characters, max is {max})"
+                )
+            }
+            Self::InvalidCharacter { ch, position } => {
+                write!(f, "invalid character {ch:?} in start_param at {position}")
+            }
+            Self::InvalidEncoding => f.write_str("start_param is not valid base64url"),
+            Self::InvalidJson(e) => write!(f, "invalid json in start_param: {e}"),
+            Self::Malformed => f.write_str("malformed signed start_param"),
+            Self::Forged => f.write_str("start_param signature does not match the data"),
+            Self::Expired => f.write_str("start_param has expired"),
+        }
+    }
+}
+
+impl std::error::Error for StartParamError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InvalidJson(e) => Some(e),
+            _ => None,
+        }
+    }
+}
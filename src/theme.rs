@@ -0,0 +1,215 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::de::IgnoredAny;
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// Theme parameters of the Telegram client, from `tgWebAppThemeParams`.
+///
+/// The parameters are set by the client, so malformed colors are ignored rather than rejected.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ThemeParams {
+    #[serde(deserialize_with = "lenient", skip_serializing_if = "Option::is_none")]
+    bg_color: Option<Rgb>,
+    #[serde(deserialize_with = "lenient", skip_serializing_if = "Option::is_none")]
+    text_color: Option<Rgb>,
+    #[serde(deserialize_with = "lenient", skip_serializing_if = "Option::is_none")]
+    hint_color: Option<Rgb>,
+    #[serde(deserialize_with = "lenient", skip_serializing_if = "Option::is_none")]
+    link_color: Option<Rgb>,
+    #[serde(deserialize_with = "lenient", skip_serializing_if = "Option::is_none")]
+    button_color: Option<Rgb>,
+    #[serde(deserialize_with = "lenient", skip_serializing_if = "Option::is_none")]
+    button_text_color: Option<Rgb>,
+    #[serde(deserialize_with = "lenient", skip_serializing_if = "Option::is_none")]
+    secondary_bg_color: Option<Rgb>,
+    #[serde(deserialize_with = "lenient", skip_serializing_if = "Option::is_none")]
+    header_bg_color: Option<Rgb>,
+    #[serde(deserialize_with = "lenient", skip_serializing_if = "Option::is_none")]
+    bottom_bar_bg_color: Option<Rgb>,
+    #[serde(deserialize_with = "lenient", skip_serializing_if = "Option::is_none")]
+    accent_text_color: Option<Rgb>,
+    #[serde(deserialize_with = "lenient", skip_serializing_if = "Option::is_none")]
+    section_bg_color: Option<Rgb>,
+    #[serde(deserialize_with = "lenient", skip_serializing_if = "Option::is_none")]
+    section_header_text_color: Option<Rgb>,
+    #[serde(deserialize_with = "lenient", skip_serializing_if = "Option::is_none")]
+    section_separator_color: Option<Rgb>,
+    #[serde(deserialize_with = "lenient", skip_serializing_if = "Option::is_none")]
+    subtitle_text_color: Option<Rgb>,
+    #[serde(deserialize_with = "lenient", skip_serializing_if = "Option::is_none")]
+    destructive_text_color: Option<Rgb>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ColorScheme {
+    Light,
+    Dark,
+}
+
+/// An RGB color, (de)serialized as `#rrggbb`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Rgb {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+/// The error returned when a color is not in the `#rrggbb` format.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseRgbError(String);
+
+impl ThemeParams {
+    pub fn bg_color(&self) -> Option<Rgb> {
+        self.bg_color
+    }
+
+    pub fn text_color(&self) -> Option<Rgb> {
+        self.text_color
+    }
+
+    pub fn hint_color(&self) -> Option<Rgb> {
+        self.hint_color
+    }
+
+    pub fn link_color(&self) -> Option<Rgb> {
+        self.link_color
+    }
+
+    pub fn button_color(&self) -> Option<Rgb> {
+        self.button_color
+    }
+
+    pub fn button_text_color(&self) -> Option<Rgb> {
+        self.button_text_color
+    }
+
+    pub fn secondary_bg_color(&self) -> Option<Rgb> {
+        self.secondary_bg_color
+    }
+
+    pub fn header_bg_color(&self) -> Option<Rgb> {
+        self.header_bg_color
+    }
+
+    pub fn bottom_bar_bg_color(&self) -> Option<Rgb> {
+        self.bottom_bar_bg_color
+    }
+
+    pub fn accent_text_color(&self) -> Option<Rgb> {
+        self.accent_text_color
+    }
+
+    pub fn section_bg_color(&self) -> Option<Rgb> {
+        self.section_bg_color
+    }
+
+    pub fn section_header_text_color(&self) -> Option<Rgb> {
+        self.section_header_text_color
+    }
+
+    pub fn section_separator_color(&self) -> Option<Rgb> {
+        self.section_separator_color
+    }
+
+    pub fn subtitle_text_color(&self) -> Option<Rgb> {
+        self.subtitle_text_color
+    }
+
+    pub fn destructive_text_color(&self) -> Option<Rgb> {
+        self.destructive_text_color
+    }
+
+    /// Whether the theme is light or dark, judging by the background color.
+    pub fn color_scheme(&self) -> Option<ColorScheme> {
+        self.bg_color.map(Rgb::color_scheme)
+    }
+}
+
+impl Rgb {
+    pub fn new(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+
+    /// The relative luminance, from 0 for black to 1 for white.
+    pub fn luminance(self) -> f64 {
+        fn linear(x: u8) -> f64 {
+            let x = f64::from(x) / 255.0;
+            if x <= 0.04045 {
+                x / 12.92
+            } else {
+                ((x + 0.055) / 1.055).powf(2.4)
+            }
+        }
+        0.2126 * linear(self.r) + 0.7152 * linear(self.g) + 0.0722 * linear(self.b)
+    }
+
+    /// Whether black or white text is more readable on this color.
+    pub fn color_scheme(self) -> ColorScheme {
+        // The luminance for which the contrast ratios with black and white are equal.
+        if self.luminance() > 0.179 {
+            ColorScheme::Light
+        } else {
+            ColorScheme::Dark
+        }
+    }
+}
+
+/// Deserialize a color, ignoring a malformed one.
+fn lenient<'de, D: Deserializer<'de>>(d: D) -> Result<Option<Rgb>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Lenient {
+        Rgb(Rgb),
+        Other(IgnoredAny),
+    }
+
+    match Lenient::deserialize(d)? {
+        Lenient::Rgb(x) => Ok(Some(x)),
+        Lenient::Other(_) => Ok(None),
+    }
+}
+
+impl FromStr for Rgb {
+    type Err = ParseRgbError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || ParseRgbError(s.to_owned());
+        let hex = s.strip_prefix('#').ok_or_else(err)?;
+        if hex.len() != 6 || !hex.bytes().all(|x| x.is_ascii_hexdigit()) {
+            return Err(err());
+        }
+        let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).map_err(|_e| err());
+        Ok(Self::new(channel(0)?, channel(2)?, channel(4)?))
+    }
+}
+
+impl TryFrom<String> for Rgb {
+    type Error = ParseRgbError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<Rgb> for String {
+    fn from(value: Rgb) -> Self {
+        value.to_string()
+    }
+}
+
+impl fmt::Display for Rgb {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+}
+
+impl fmt::Display for ParseRgbError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid color '{}', expected #rrggbb", self.0)
+    }
+}
+
+impl std::error::Error for ParseRgbError {}
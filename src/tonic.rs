@@ -0,0 +1,80 @@
+//! A `tonic` interceptor validating initData.
+
+use tonic::metadata::{AsciiMetadataKey, MetadataValue};
+use tonic::service::Interceptor;
+use tonic::{Request, Status};
+
+use crate::{Error, Validator};
+
+/// The default metadata key containing initData.
+pub const DEFAULT_METADATA_KEY: &str = "x-telegram-init-data";
+
+/// An [`Interceptor`] that validates initData from the request metadata.
+///
+/// The verified [`WebAppUser`](crate::WebAppUser) and
+/// [`WebAppInitData`](crate::WebAppInitData) are inserted into the request extensions. Requests
+/// with missing or invalid initData, or without the `user` field, fail with
+/// `Status::unauthenticated`.
+#[derive(Debug, Clone)]
+pub struct TelegramInterceptor {
+    validator: Validator,
+    key: AsciiMetadataKey,
+}
+
+impl TelegramInterceptor {
+    pub fn new(validator: Validator) -> Self {
+        Self {
+            validator,
+            key: AsciiMetadataKey::from_static(DEFAULT_METADATA_KEY),
+        }
+    }
+
+    /// Read initData from the metadata `key` instead of [`DEFAULT_METADATA_KEY`].
+    ///
+    /// Use e.g. [`AsciiMetadataKey::from_static`], which rejects keys that never match, such as
+    /// ones with uppercase characters.
+    pub fn metadata_key(mut self, key: AsciiMetadataKey) -> Self {
+        self.key = key;
+        self
+    }
+}
+
+impl Interceptor for TelegramInterceptor {
+    fn call(&mut self, mut request: Request<()>) -> Result<Request<()>, Status> {
+        let init_data = request
+            .metadata()
+            .get(&self.key)
+            .ok_or_else(|| unauthenticated("not_present", "missing init data".to_owned()))?
+            .to_str()
+            .map_err(|_e| {
+                unauthenticated("malformed", "init data is not valid ascii".to_owned())
+            })?;
+        let data = self
+            .validator
+            .validate(init_data.as_bytes())
+            .map_err(invalid)?;
+        let user = data
+            .user
+            .clone()
+            .ok_or_else(|| invalid(Error::MissingField("user")))?;
+        request.extensions_mut().insert(user);
+        request.extensions_mut().insert(data);
+        Ok(request)
+    }
+}
+
+/// The status message is the error message, and the [`Error::code`] is put into the
+/// `x-telegram-auth-error` metadata.
+fn invalid(e: Error) -> Status {
+    unauthenticated(e.code(), format!("invalid init data: {e}"))
+}
+
+/// An unauthenticated status with `code` in the `x-telegram-auth-error` metadata, which is
+/// `not_present` for missing and `malformed` for non-ascii initData.
+fn unauthenticated(code: &'static str, message: String) -> Status {
+    let mut status = Status::unauthenticated(message);
+    status
+        .metadata_mut()
+        .insert("x-telegram-auth-error", MetadataValue::from_static(code));
+    status
+}
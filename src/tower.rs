@@ -0,0 +1,198 @@
+//! A `tower` middleware validating initData.
+//!
+//! [`InitDataLayer`] validates initData of incoming requests and inserts the verified
+//! [`WebAppInitData`] into the request extensions. Invalid requests are rejected with
+//! `401 Unauthorized` or `400 Bad Request` and never reach the inner service.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use http::{HeaderValue, Request, Response, StatusCode, header};
+use tower_layer::Layer;
+use tower_service::Service;
+
+use crate::http::{ExtractError, InitDataExtractor};
+use crate::{Validator, WebAppInitData};
+
+#[derive(Debug, Clone)]
+struct Config {
+    validator: Validator,
+    extractor: InitDataExtractor,
+    exempt: Vec<String>,
+    exempt_prefixes: Vec<String>,
+}
+
+/// A [`Layer`] that validates initData.
+///
+/// By default initData is read from the `Authorization: tma <initData>` header. Body sources of
+/// the [`InitDataExtractor`] are ignored, since the body is not buffered.
+#[derive(Debug, Clone)]
+pub struct InitDataLayer {
+    config: Arc<Config>,
+}
+
+impl InitDataLayer {
+    pub fn new(validator: Validator) -> Self {
+        Self {
+            config: Arc::new(Config {
+                validator,
+                extractor: InitDataExtractor::default(),
+                exempt: Vec::new(),
+                exempt_prefixes: Vec::new(),
+            }),
+        }
+    }
+
+    /// Locate initData with `extractor` instead of the default one.
+    pub fn extractor(mut self, extractor: InitDataExtractor) -> Self {
+        self.config_mut().extractor = extractor;
+        self
+    }
+
+    /// Do not require initData for requests to `path`.
+    ///
+    /// Paths with `.` or `..` segments, percent-encoded or not, are never exempt.
+    pub fn exempt(mut self, path: impl Into<String>) -> Self {
+        self.config_mut().exempt.push(path.into());
+        self
+    }
+
+    /// Do not require initData for requests to `prefix` and the paths under it, e.g. `/public`
+    /// exempts `/public` and `/public/logo.png`, but not `/publicity`.
+    pub fn exempt_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.config_mut().exempt_prefixes.push(prefix.into());
+        self
+    }
+
+    fn config_mut(&mut self) -> &mut Config {
+        Arc::make_mut(&mut self.config)
+    }
+}
+
+impl<S> Layer<S> for InitDataLayer {
+    type Service = InitDataService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        InitDataService {
+            inner,
+            config: self.config.clone(),
+        }
+    }
+}
+
+/// The [`Service`] created by [`InitDataLayer`].
+#[derive(Debug, Clone)]
+pub struct InitDataService<S> {
+    inner: S,
+    config: Arc<Config>,
+}
+
+impl Config {
+    fn is_exempt(&self, path: &str) -> bool {
+        // A path the inner service may resolve differently, e.g. `/public/../admin`, is never
+        // exempt.
+        let ambiguous = path.split('/').any(|x| {
+            let x = percent_encoding::percent_decode_str(x).decode_utf8_lossy();
+            x == "." || x == ".." || x.contains(['/', '\\'])
+        });
+        if ambiguous {
+            return false;
+        }
+        self.exempt.iter().any(|x| x == path)
+            || self.exempt_prefixes.iter().any(|prefix| {
+                // Only match whole segments, so `/public` does not exempt `/publicity`.
+                path.strip_prefix(prefix.as_str()).is_some_and(|rest| {
+                    rest.is_empty() || rest.starts_with('/') || prefix.ends_with('/')
+                })
+            })
+    }
+}
+
+impl<S, ReqBody, ResBody> Service<Request<ReqBody>> for InitDataService<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<ResBody>>,
+    ResBody: Default,
+{
+    type Response = Response<ResBody>;
+    type Error = S::Error;
+    type Future = ResponseFuture<S::Future, ResBody>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: Request<ReqBody>) -> Self::Future {
+        if self.config.is_exempt(req.uri().path()) {
+            return ResponseFuture::inner(self.inner.call(req));
+        }
+
+        let status = match self.config.extractor.validate(&self.config.validator, &req) {
+            Ok(data) => {
+                req.extensions_mut().insert::<WebAppInitData>(data);
+                return ResponseFuture::inner(self.inner.call(req));
+            }
+            Err(ExtractError::NotPresent) => StatusCode::UNAUTHORIZED,
+            Err(ExtractError::Invalid(e)) if e.is_unauthorized() => StatusCode::UNAUTHORIZED,
+            Err(ExtractError::Invalid(_)) => StatusCode::BAD_REQUEST,
+        };
+
+        let mut response = Response::new(ResBody::default());
+        *response.status_mut() = status;
+        if status == StatusCode::UNAUTHORIZED {
+            response
+                .headers_mut()
+                .insert(header::WWW_AUTHENTICATE, HeaderValue::from_static("tma"));
+        }
+        ResponseFuture::rejected(response)
+    }
+}
+
+pin_project_lite::pin_project! {
+    /// The response future of [`InitDataService`].
+    pub struct ResponseFuture<F, B> {
+        #[pin]
+        kind: Kind<F, B>,
+    }
+}
+
+pin_project_lite::pin_project! {
+    #[project = KindProj]
+    enum Kind<F, B> {
+        Inner { #[pin] future: F },
+        Rejected { response: Option<Response<B>> },
+    }
+}
+
+impl<F, B> ResponseFuture<F, B> {
+    fn inner(future: F) -> Self {
+        Self {
+            kind: Kind::Inner { future },
+        }
+    }
+
+    fn rejected(response: Response<B>) -> Self {
+        Self {
+            kind: Kind::Rejected {
+                response: Some(response),
+            },
+        }
+    }
+}
+
+impl<F, B, E> Future for ResponseFuture<F, B>
+where
+    F: Future<Output = Result<Response<B>, E>>,
+{
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.project().kind.project() {
+            KindProj::Inner { future } => future.poll(cx),
+            KindProj::Rejected { response } => {
+                Poll::Ready(Ok(response.take().expect("polled after completion")))
+            }
+        }
+    }
+}
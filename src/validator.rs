@@ -0,0 +1,87 @@
+use std::fmt;
+use std::sync::Arc;
+
+use crate::{Environment, Error, KNOWN_FIELDS, SignedForm, ValidationOptions, WebAppInitData};
+
+#[derive(Clone)]
+enum Key {
+    /// `HMAC("WebAppData", token)`
+    Hmac([u8; 32]),
+    Ed25519 {
+        bot_id: u64,
+        public_key: [u8; 32],
+    },
+}
+
+/// A reusable initData validator.
+///
+/// Construct it once, e.g. at startup, and share it between requests. The secret key is derived
+/// from the bot token only once, and cloning is cheap, so it may be used as or extracted from the
+/// state of a web framework on every request.
+#[derive(Clone)]
+pub struct Validator {
+    key: Key,
+    options: Arc<ValidationOptions>,
+}
+
+impl fmt::Debug for Validator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut s = f.debug_struct("Validator");
+        if let Key::Ed25519 { bot_id, .. } = &self.key {
+            s.field("bot_id", bot_id);
+        }
+        s.field("options", &self.options).finish_non_exhaustive()
+    }
+}
+
+impl Validator {
+    /// Validate the `hash` field using the bot token.
+    pub fn new(token: &str) -> Self {
+        Self {
+            key: Key::Hmac(SignedForm::web_app_secret_key(token)),
+            options: Arc::default(),
+        }
+    }
+
+    /// Validate the `signature` field using Telegram's public key, without knowing the bot token.
+    pub fn third_party(bot_id: u64, env: Environment) -> Self {
+        Self {
+            key: Key::Ed25519 {
+                bot_id,
+                public_key: env.public_key(),
+            },
+            options: Arc::default(),
+        }
+    }
+
+    /// Like [`third_party`](Self::third_party), but with a custom Ed25519 public key.
+    pub fn third_party_with_key(bot_id: u64, public_key: [u8; 32]) -> Self {
+        Self {
+            key: Key::Ed25519 { bot_id, public_key },
+            options: Arc::default(),
+        }
+    }
+
+    pub fn with_options(mut self, options: ValidationOptions) -> Self {
+        self.options = Arc::new(options);
+        self
+    }
+
+    pub fn options(&self) -> &ValidationOptions {
+        &self.options
+    }
+
+    pub fn validate(&self, raw: &[u8]) -> Result<WebAppInitData, Error> {
+        let form = SignedForm::parse_known(raw, &self.options, Some(KNOWN_FIELDS))?;
+        match &self.key {
+            Key::Hmac(secret_key) => form.verify(secret_key)?,
+            Key::Ed25519 { bot_id, public_key } => form.verify_signature(*bot_id, public_key)?,
+        }
+
+        let fields = form.into_fields();
+        self.options.check_required(&fields)?;
+        let data = WebAppInitData::from_fields(fields)?;
+        self.options.check_freshness(data.auth_date)?;
+        Ok(data)
+    }
+}
@@ -0,0 +1,183 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// The Bot API version supported by the Telegram client, from `tgWebAppVersion`.
+///
+/// Versions are compared numerically, so `7.10` is newer than `7.2`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct WebAppVersion {
+    major: u16,
+    minor: u16,
+}
+
+/// A capability of Mini Apps that is only available in some versions of the client.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Capability {
+    BackButton,
+    HapticFeedback,
+    Invoices,
+    Popups,
+    ClosingConfirmation,
+    QrScanner,
+    ReadClipboard,
+    SwitchInlineQuery,
+    CloudStorage,
+    RequestWriteAccess,
+    RequestContact,
+    SettingsButton,
+    Biometrics,
+    DisableVerticalSwipes,
+    ShareToStory,
+    SecondaryButton,
+    Fullscreen,
+    HomeScreen,
+    EmojiStatus,
+    Location,
+    MotionSensors,
+    DownloadFile,
+    ShareMessage,
+    OrientationLock,
+    /// The `signature` field of initData, for validation without the bot token.
+    InitDataSignature,
+    DeviceStorage,
+    SecureStorage,
+}
+
+/// The error returned when a version is not in the `major.minor` format.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseVersionError(String);
+
+impl WebAppVersion {
+    pub const fn new(major: u16, minor: u16) -> Self {
+        Self { major, minor }
+    }
+
+    pub fn major(self) -> u16 {
+        self.major
+    }
+
+    pub fn minor(self) -> u16 {
+        self.minor
+    }
+
+    pub fn supports(self, capability: Capability) -> bool {
+        self >= capability.min_version()
+    }
+
+    /// All of the capabilities supported by this version.
+    pub fn capabilities(self) -> impl Iterator<Item = Capability> {
+        Capability::ALL
+            .iter()
+            .copied()
+            .filter(move |x| self.supports(*x))
+    }
+}
+
+impl Capability {
+    /// All of the capabilities, from the oldest to the newest.
+    pub const ALL: &[Capability] = &[
+        Self::BackButton,
+        Self::HapticFeedback,
+        Self::Invoices,
+        Self::Popups,
+        Self::ClosingConfirmation,
+        Self::QrScanner,
+        Self::ReadClipboard,
+        Self::SwitchInlineQuery,
+        Self::CloudStorage,
+        Self::RequestWriteAccess,
+        Self::RequestContact,
+        Self::SettingsButton,
+        Self::Biometrics,
+        Self::DisableVerticalSwipes,
+        Self::ShareToStory,
+        Self::SecondaryButton,
+        Self::Fullscreen,
+        Self::HomeScreen,
+        Self::EmojiStatus,
+        Self::Location,
+        Self::MotionSensors,
+        Self::DownloadFile,
+        Self::ShareMessage,
+        Self::OrientationLock,
+        Self::InitDataSignature,
+        Self::DeviceStorage,
+        Self::SecureStorage,
+    ];
+
+    /// The first version supporting this capability.
+    pub const fn min_version(self) -> WebAppVersion {
+        let (major, minor) = match self {
+            Self::BackButton | Self::HapticFeedback | Self::Invoices => (6, 1),
+            Self::Popups | Self::ClosingConfirmation => (6, 2),
+            Self::QrScanner | Self::ReadClipboard => (6, 4),
+            Self::SwitchInlineQuery => (6, 7),
+            Self::CloudStorage | Self::RequestWriteAccess | Self::RequestContact => (6, 9),
+            Self::SettingsButton => (7, 0),
+            Self::Biometrics => (7, 2),
+            Self::DisableVerticalSwipes => (7, 7),
+            Self::ShareToStory => (7, 8),
+            Self::SecondaryButton => (7, 10),
+            Self::Fullscreen
+            | Self::HomeScreen
+            | Self::EmojiStatus
+            | Self::Location
+            | Self::MotionSensors
+            | Self::DownloadFile
+            | Self::ShareMessage
+            | Self::OrientationLock
+            | Self::InitDataSignature => (8, 0),
+            Self::DeviceStorage | Self::SecureStorage => (9, 0),
+        };
+        WebAppVersion::new(major, minor)
+    }
+}
+
+impl FromStr for WebAppVersion {
+    type Err = ParseVersionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let number = |x: &str| {
+            if x.is_empty() || !x.bytes().all(|x| x.is_ascii_digit()) {
+                return Err(ParseVersionError(s.to_owned()));
+            }
+            x.parse().map_err(|_e| ParseVersionError(s.to_owned()))
+        };
+        match s.split_once('.') {
+            Some((major, minor)) => Ok(Self::new(number(major)?, number(minor)?)),
+            None => Ok(Self::new(number(s)?, 0)),
+        }
+    }
+}
+
+impl TryFrom<String> for WebAppVersion {
+    type Error = ParseVersionError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<WebAppVersion> for String {
+    fn from(value: WebAppVersion) -> Self {
+        value.to_string()
+    }
+}
+
+impl fmt::Display for WebAppVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+impl fmt::Display for ParseVersionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid version '{}', expected major.minor", self.0)
+    }
+}
+
+impl std::error::Error for ParseVersionError {}
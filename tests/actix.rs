@@ -0,0 +1,175 @@
+#![cfg(feature = "actix-web")]
+
+mod common;
+
+use actix_web::body;
+use actix_web::http::StatusCode;
+use actix_web::http::header::{self, HeaderName};
+use actix_web::test::TestRequest;
+use actix_web::web::Data;
+use actix_web::{FromRequest, ResponseError};
+use tg_webapp_init_data::actix::{InitDataExtractor, TelegramAuthRejection};
+use tg_webapp_init_data::{Error, Validator, WebAppInitData, WebAppUser};
+
+fn request() -> TestRequest {
+    TestRequest::default().app_data(Data::new(Validator::new(common::TOKEN)))
+}
+
+fn body(e: &TelegramAuthRejection) -> String {
+    let body = common::block_on(body::to_bytes(e.error_response().into_body())).unwrap();
+    String::from_utf8(body.to_vec()).unwrap()
+}
+
+fn extract<T: FromRequest>(request: TestRequest) -> Result<T, T::Error> {
+    let (request, mut payload) = request.to_http_parts();
+    common::block_on(T::from_request(&request, &mut payload))
+}
+
+#[test]
+fn authorization() {
+    let raw = common::sign(&[("auth_date", "1700000000"), ("query_id", "AAH")]);
+    let data: WebAppInitData =
+        extract(request().insert_header((header::AUTHORIZATION, format!("tma {raw}")))).unwrap();
+    assert_eq!(data.query_id(), Some("AAH"));
+}
+
+#[test]
+fn source_order() {
+    let first = common::sign(&[("auth_date", "1700000000"), ("query_id", "first")]);
+    let second = common::sign(&[("auth_date", "1700000000"), ("query_id", "second")]);
+    let query = form_urlencoded::Serializer::new(String::new())
+        .append_pair("init_data", &second)
+        .finish();
+    let config = InitDataExtractor::empty()
+        .header(HeaderName::from_static("x-init-data"))
+        .query("init_data")
+        .json_field("initData");
+
+    let data: WebAppInitData = extract(
+        request()
+            .app_data(config.clone())
+            .uri(&format!("/?{query}"))
+            .insert_header(("x-init-data", first.as_str())),
+    )
+    .unwrap();
+    assert_eq!(data.query_id(), Some("first"));
+
+    let data: WebAppInitData = extract(
+        request()
+            .app_data(config.clone())
+            .uri(&format!("/?{query}"))
+            .set_json(serde_json::json!({ "initData": first })),
+    )
+    .unwrap();
+    assert_eq!(data.query_id(), Some("second"));
+
+    let data: WebAppInitData = extract(
+        request()
+            .app_data(config)
+            .set_json(serde_json::json!({ "initData": first })),
+    )
+    .unwrap();
+    assert_eq!(data.query_id(), Some("first"));
+
+    // The default config only looks at the Authorization header.
+    assert!(matches!(
+        extract::<WebAppInitData>(request().insert_header(("x-init-data", first.as_str()))),
+        Err(TelegramAuthRejection::NotPresent)
+    ));
+}
+
+#[test]
+fn cookie_and_form_field() {
+    let raw = common::sign(&[("auth_date", "1700000000"), ("query_id", "AAH")]);
+    let encoded: String = form_urlencoded::byte_serialize(raw.as_bytes()).collect();
+
+    let extractor = InitDataExtractor::empty().cookie("init_data");
+    let data: WebAppInitData = extract(
+        request()
+            .app_data(extractor)
+            .insert_header((header::COOKIE, format!("a=b; init_data={encoded}"))),
+    )
+    .unwrap();
+    assert_eq!(data.query_id(), Some("AAH"));
+
+    let extractor = InitDataExtractor::empty().form_field("init_data");
+    let data: WebAppInitData = extract(
+        request()
+            .app_data(extractor)
+            .set_form([("init_data", raw.as_str())]),
+    )
+    .unwrap();
+    assert_eq!(data.query_id(), Some("AAH"));
+}
+
+#[test]
+fn user() {
+    let raw = common::sign(&[
+        ("auth_date", "1700000000"),
+        ("user", r#"{"id":1,"first_name":"A"}"#),
+    ]);
+    let user: WebAppUser =
+        extract(request().insert_header((header::AUTHORIZATION, format!("tma {raw}")))).unwrap();
+    assert_eq!(user.id(), 1);
+
+    let raw = common::sign(&[("auth_date", "1700000000")]);
+    assert!(matches!(
+        extract::<WebAppUser>(
+            request().insert_header((header::AUTHORIZATION, format!("tma {raw}")))
+        ),
+        Err(TelegramAuthRejection::Invalid(Error::MissingField("user")))
+    ));
+}
+
+#[test]
+fn rejections() {
+    let e = extract::<WebAppInitData>(request()).unwrap_err();
+    assert_eq!(e.status_code(), StatusCode::UNAUTHORIZED);
+    assert_eq!(
+        e.error_response()
+            .headers()
+            .get(header::WWW_AUTHENTICATE)
+            .unwrap(),
+        "tma"
+    );
+    assert_eq!(
+        body(&e),
+        r#"{"code":"not_present","field":null,"message":"missing init data"}"#
+    );
+
+    let raw = common::sign(&[("auth_date", "1700000000")]).replace("1700000000", "1700000001");
+    let e = extract::<WebAppInitData>(
+        request().insert_header((header::AUTHORIZATION, format!("tma {raw}"))),
+    )
+    .unwrap_err();
+    assert!(matches!(
+        e,
+        TelegramAuthRejection::Invalid(Error::InvalidHash)
+    ));
+    assert_eq!(e.status_code(), StatusCode::UNAUTHORIZED);
+    assert_eq!(
+        e.error_response()
+            .headers()
+            .get(header::WWW_AUTHENTICATE)
+            .unwrap(),
+        "tma"
+    );
+
+    let raw = common::sign(&[("auth_date", "1700000000"), ("user", "{")]);
+    let e = extract::<WebAppInitData>(
+        request().insert_header((header::AUTHORIZATION, format!("tma {raw}"))),
+    )
+    .unwrap_err();
+    assert_eq!(e.status_code(), StatusCode::BAD_REQUEST);
+    assert!(body(&e).starts_with(r#"{"code":"invalid_json","field":"user","#));
+    assert!(
+        !e.error_response()
+            .headers()
+            .contains_key(header::WWW_AUTHENTICATE)
+    );
+
+    let e = extract::<WebAppInitData>(TestRequest::default()).unwrap_err();
+    assert!(matches!(e, TelegramAuthRejection::MissingValidator));
+    assert_eq!(e.status_code(), StatusCode::INTERNAL_SERVER_ERROR);
+    assert!(body(&e).starts_with(r#"{"code":"missing_validator","#));
+}
@@ -0,0 +1,167 @@
+#![cfg(feature = "async-graphql")]
+
+mod common;
+
+use async_graphql::{Context, EmptyMutation, EmptySubscription, Object, Request, Schema, Value};
+use http::{HeaderMap, HeaderValue, header};
+use tg_webapp_init_data::async_graphql::{TelegramGuard, authenticate, authenticate_with};
+use tg_webapp_init_data::http::InitDataExtractor;
+use tg_webapp_init_data::{Error, Validator, WebAppInitData};
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn public(&self) -> &str {
+        "public"
+    }
+
+    async fn query_id(&self, ctx: &Context<'_>) -> Option<String> {
+        ctx.data_opt::<WebAppInitData>()?
+            .query_id()
+            .map(str::to_owned)
+    }
+
+    #[graphql(guard = "TelegramGuard::user()")]
+    async fn user(&self) -> &str {
+        "user"
+    }
+
+    #[graphql(guard = "TelegramGuard::user().premium()")]
+    async fn premium(&self) -> &str {
+        "premium"
+    }
+
+    #[graphql(guard = "TelegramGuard::user().not_bot()")]
+    async fn human(&self) -> &str {
+        "human"
+    }
+
+    #[graphql(guard = "TelegramGuard::custom(|x| x.id() == 1)")]
+    async fn first(&self) -> &str {
+        "first"
+    }
+}
+
+fn headers(user: Option<&str>) -> HeaderMap {
+    let mut fields = vec![("auth_date", "1700000000")];
+    fields.extend(user.map(|x| ("user", x)));
+    let raw = common::sign(&fields);
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        header::AUTHORIZATION,
+        HeaderValue::from_str(&format!("tma {raw}")).unwrap(),
+    );
+    headers
+}
+
+/// Run `{ field }`, returning the value or the error code.
+fn run(field: &str, headers: &HeaderMap) -> Result<String, String> {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let request = authenticate(
+        Request::new(format!("{{ {field} }}")),
+        &Validator::new(common::TOKEN),
+        headers,
+    )
+    .unwrap();
+    let response = common::block_on(schema.execute(request));
+    match response.errors.first() {
+        Some(e) => match e.extensions.as_ref().and_then(|x| x.get("code")) {
+            Some(Value::String(code)) => Err(code.clone()),
+            _ => panic!("{e:?}"),
+        },
+        None => Ok(response.data.to_string()),
+    }
+}
+
+#[test]
+fn authenticate_validates_header() {
+    assert!(
+        authenticate(
+            Request::new("{ public }"),
+            &Validator::new(common::TOKEN),
+            &HeaderMap::new()
+        )
+        .is_ok()
+    );
+
+    let mut headers = headers(None);
+    let tampered = headers[header::AUTHORIZATION]
+        .to_str()
+        .unwrap()
+        .replace("1700000000", "1700000001");
+    headers.insert(
+        header::AUTHORIZATION,
+        HeaderValue::from_str(&tampered).unwrap(),
+    );
+    assert!(matches!(
+        authenticate(
+            Request::new("{ public }"),
+            &Validator::new(common::TOKEN),
+            &headers
+        ),
+        Err(Error::InvalidHash)
+    ));
+}
+
+#[test]
+fn authenticate_with_extractor() {
+    let raw = common::sign(&[("auth_date", "1700000000"), ("query_id", "AAH")]);
+    let query = form_urlencoded::Serializer::new(String::new())
+        .append_pair("init_data", &raw)
+        .finish();
+    let (parts, ()) = http::Request::get(format!("/graphql?{query}"))
+        .body(())
+        .unwrap()
+        .into_parts();
+    let validator = Validator::new(common::TOKEN);
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let query_id = |extractor: InitDataExtractor| {
+        let request =
+            authenticate_with(Request::new("{ queryId }"), &validator, &extractor, &parts).unwrap();
+        common::block_on(schema.execute(request)).data.to_string()
+    };
+    assert_eq!(
+        query_id(InitDataExtractor::empty().query("init_data")),
+        r#"{queryId: "AAH"}"#
+    );
+    assert_eq!(query_id(InitDataExtractor::default()), "{queryId: null}");
+}
+
+#[test]
+fn guard() {
+    let user = headers(Some(r#"{"id":1,"first_name":"A"}"#));
+    assert_eq!(
+        run("public", &HeaderMap::new()),
+        Ok(r#"{public: "public"}"#.to_owned())
+    );
+    assert_eq!(
+        run("user", &HeaderMap::new()),
+        Err("UNAUTHENTICATED".to_owned())
+    );
+    assert_eq!(
+        run("user", &headers(None)),
+        Err("UNAUTHENTICATED".to_owned())
+    );
+    assert_eq!(run("user", &user), Ok(r#"{user: "user"}"#.to_owned()));
+
+    assert_eq!(run("premium", &user), Err("FORBIDDEN".to_owned()));
+    let premium = headers(Some(r#"{"id":1,"first_name":"A","is_premium":true}"#));
+    assert_eq!(
+        run("premium", &premium),
+        Ok(r#"{premium: "premium"}"#.to_owned())
+    );
+
+    assert_eq!(run("human", &user), Ok(r#"{human: "human"}"#.to_owned()));
+    let bot = headers(Some(r#"{"id":1,"first_name":"A","is_bot":true}"#));
+    assert_eq!(run("human", &bot), Err("FORBIDDEN".to_owned()));
+
+    assert_eq!(run("first", &user), Ok(r#"{first: "first"}"#.to_owned()));
+    let other = headers(Some(r#"{"id":2,"first_name":"B"}"#));
+    assert_eq!(run("first", &other), Err("FORBIDDEN".to_owned()));
+    assert_eq!(
+        run("first", &HeaderMap::new()),
+        Err("UNAUTHENTICATED".to_owned())
+    );
+}
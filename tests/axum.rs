@@ -0,0 +1,105 @@
+#![cfg(feature = "axum")]
+
+mod common;
+
+use axum_core::extract::FromRequestParts;
+use axum_core::response::{IntoResponse, Response};
+use http::{Request, StatusCode, header};
+use http_body_util::BodyExt;
+use tg_webapp_init_data::Validator;
+use tg_webapp_init_data::axum::{TelegramAuth, TelegramAuthRejection};
+use tg_webapp_init_data::http::InitDataExtractor;
+
+fn extract(authorization: Option<&str>) -> Result<TelegramAuth, TelegramAuthRejection> {
+    let mut request = Request::builder();
+    if let Some(authorization) = authorization {
+        request = request.header(header::AUTHORIZATION, authorization);
+    }
+    extract_request(request.body(()).unwrap())
+}
+
+fn extract_request(request: Request<()>) -> Result<TelegramAuth, TelegramAuthRejection> {
+    let (mut parts, ()) = request.into_parts();
+    common::block_on(TelegramAuth::from_request_parts(
+        &mut parts,
+        &Validator::new(common::TOKEN),
+    ))
+}
+
+fn body(response: Response) -> String {
+    let bytes = common::block_on(response.into_body().collect())
+        .unwrap()
+        .to_bytes();
+    String::from_utf8(bytes.to_vec()).unwrap()
+}
+
+#[test]
+fn valid() {
+    let raw = common::sign(&[("auth_date", "1700000000"), ("query_id", "AAH")]);
+    let TelegramAuth(data) = extract(Some(&format!("tma {raw}"))).unwrap();
+    assert_eq!(data.query_id(), Some("AAH"));
+}
+
+#[test]
+fn extractor_from_extensions() {
+    let raw = common::sign(&[("auth_date", "1700000000"), ("query_id", "AAH")]);
+    let query = form_urlencoded::Serializer::new(String::new())
+        .append_pair("init_data", &raw)
+        .finish();
+    let request = || Request::get(format!("/?{query}"));
+
+    assert!(matches!(
+        extract_request(request().body(()).unwrap()),
+        Err(TelegramAuthRejection::NotPresent)
+    ));
+    let TelegramAuth(data) = extract_request(
+        request()
+            .extension(InitDataExtractor::empty().query("init_data"))
+            .body(())
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(data.query_id(), Some("AAH"));
+}
+
+#[test]
+fn missing_header() {
+    let response = extract(None).unwrap_err().into_response();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(response.headers()[header::WWW_AUTHENTICATE], "tma");
+    assert_eq!(response.headers()[header::CONTENT_TYPE], "application/json");
+    assert_eq!(
+        body(response),
+        r#"{"code":"not_present","field":null,"message":"missing init data"}"#
+    );
+
+    let response = extract(Some("Bearer x")).unwrap_err().into_response();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(response.headers()[header::WWW_AUTHENTICATE], "tma");
+}
+
+#[test]
+fn invalid_hash() {
+    let raw = common::sign(&[("auth_date", "1700000000")]).replace("1700000000", "1700000001");
+    let response = extract(Some(&format!("tma {raw}")))
+        .unwrap_err()
+        .into_response();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(response.headers()[header::WWW_AUTHENTICATE], "tma");
+    assert_eq!(response.headers()[header::CONTENT_TYPE], "application/json");
+    assert_eq!(
+        body(response),
+        r#"{"code":"invalid_hash","field":"hash","message":"hash does not match the data"}"#
+    );
+}
+
+#[test]
+fn malformed_data() {
+    let raw = common::sign(&[("auth_date", "1700000000"), ("user", "{")]);
+    let response = extract(Some(&format!("tma {raw}")))
+        .unwrap_err()
+        .into_response();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert!(!response.headers().contains_key(header::WWW_AUTHENTICATE));
+    assert!(body(response).starts_with(r#"{"code":"invalid_json","field":"user","#));
+}
@@ -0,0 +1,121 @@
+#![cfg(feature = "signing")]
+
+mod common;
+
+use std::time::{Duration, SystemTime};
+
+use common::TOKEN;
+use tg_webapp_init_data::{
+    ChatType, Error, Validator, WebAppChat, WebAppInitDataBuilder, WebAppUser, ed25519_public_key,
+};
+
+const BOT_ID: u64 = 123456;
+const SEED: [u8; 32] = [7; 32];
+
+fn builder() -> WebAppInitDataBuilder {
+    let user = WebAppUser::new(42, "Jo & Co").with_is_premium(true);
+    WebAppInitDataBuilder::new()
+        .query_id("AAH")
+        .user(&user)
+        .chat_type(ChatType::Supergroup)
+        .start_param("ref_1")
+        .can_send_after(Duration::from_secs(5))
+        .auth_date(SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000))
+}
+
+#[test]
+fn round_trip() {
+    let data = Validator::new(TOKEN)
+        .validate(builder().build(TOKEN).as_bytes())
+        .unwrap();
+    assert_eq!(data.query_id(), Some("AAH"));
+    assert_eq!(data.user().unwrap().id(), 42);
+    assert_eq!(data.user().unwrap().first_name(), "Jo & Co");
+    assert!(data.user().unwrap().is_premium());
+    assert_eq!(data.chat_type(), Some(&ChatType::Supergroup));
+    assert_eq!(data.start_param(), Some("ref_1"));
+    assert_eq!(data.can_send_after(), Some(Duration::from_secs(5)));
+    assert_eq!(
+        data.auth_date(),
+        SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000)
+    );
+}
+
+#[test]
+fn wrong_token() {
+    assert!(matches!(
+        Validator::new("123456:OTHER-TOKEN").validate(builder().build(TOKEN).as_bytes()),
+        Err(Error::InvalidHash)
+    ));
+}
+
+#[test]
+fn tampered_field() {
+    let raw = builder()
+        .build(TOKEN)
+        .replace("start_param=ref_1", "start_param=ref_2");
+    assert!(matches!(
+        Validator::new(TOKEN).validate(raw.as_bytes()),
+        Err(Error::InvalidHash)
+    ));
+}
+
+#[test]
+fn third_party_round_trip() {
+    let raw = builder().ed25519_key(BOT_ID, SEED).build(TOKEN);
+    let validator = Validator::third_party_with_key(BOT_ID, ed25519_public_key(SEED));
+    let data = validator.validate(raw.as_bytes()).unwrap();
+    assert_eq!(data.start_param(), Some("ref_1"));
+    // The hash is still valid as well.
+    assert!(Validator::new(TOKEN).validate(raw.as_bytes()).is_ok());
+}
+
+#[test]
+fn third_party_wrong_bot_id_or_key() {
+    let raw = builder().ed25519_key(BOT_ID, SEED).build(TOKEN);
+    assert!(matches!(
+        Validator::third_party_with_key(BOT_ID + 1, ed25519_public_key(SEED))
+            .validate(raw.as_bytes()),
+        Err(Error::InvalidSignature)
+    ));
+    assert!(matches!(
+        Validator::third_party_with_key(BOT_ID, ed25519_public_key([8; 32]))
+            .validate(raw.as_bytes()),
+        Err(Error::InvalidSignature)
+    ));
+}
+
+#[test]
+fn third_party_tampered_field() {
+    let raw = builder()
+        .ed25519_key(BOT_ID, SEED)
+        .build(TOKEN)
+        .replace("start_param=ref_1", "start_param=ref_2");
+    assert!(matches!(
+        Validator::third_party_with_key(BOT_ID, ed25519_public_key(SEED)).validate(raw.as_bytes()),
+        Err(Error::InvalidSignature)
+    ));
+}
+
+#[test]
+fn third_party_missing_signature() {
+    let raw = builder().build(TOKEN);
+    assert!(matches!(
+        Validator::third_party_with_key(BOT_ID, ed25519_public_key(SEED)).validate(raw.as_bytes()),
+        Err(Error::MissingField("signature"))
+    ));
+}
+
+#[test]
+fn receiver_and_chat() {
+    let receiver = WebAppUser::new(7, "Bot")
+        .with_is_bot(true)
+        .with_username("some_bot");
+    let chat = WebAppChat::new(-100, ChatType::Channel, "News").with_username("news");
+    let raw = builder().receiver(&receiver).chat(&chat).build(TOKEN);
+    let data = Validator::new(TOKEN).validate(raw.as_bytes()).unwrap();
+    assert_eq!(data.receiver(), Some(&receiver));
+    assert_eq!(data.chat(), Some(&chat));
+    assert_eq!(data.receiver().unwrap().is_bot(), Some(true));
+    assert_eq!(data.chat().unwrap().chat_type(), &ChatType::Channel);
+}
@@ -0,0 +1,50 @@
+#![allow(dead_code)]
+
+use std::pin::pin;
+use std::task::{Context, Poll, Waker};
+use std::time::{Duration, SystemTime};
+
+pub const TOKEN: &str = "123456:TEST-TOKEN";
+
+/// A point in time to use as a fixed clock, in Unix seconds.
+pub fn at(secs: u64) -> SystemTime {
+    SystemTime::UNIX_EPOCH + Duration::from_secs(secs)
+}
+
+/// Sign `fields` with `TOKEN` as Telegram does, without going through this crate.
+pub fn sign(fields: &[(&str, &str)]) -> String {
+    sign_with_key(&hmac_sha256::HMAC::mac(TOKEN, "WebAppData"), fields)
+}
+
+/// Sign `fields` with `TOKEN` as the Login Widget does.
+pub fn sign_login_widget(fields: &[(&str, &str)]) -> String {
+    sign_with_key(&hmac_sha256::Hash::hash(TOKEN.as_bytes()), fields)
+}
+
+fn sign_with_key(secret_key: &[u8; 32], fields: &[(&str, &str)]) -> String {
+    let mut sorted = fields.to_vec();
+    sorted.sort();
+    let data_check_string = sorted
+        .iter()
+        .map(|(k, v)| format!("{k}={v}"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let hash = hmac_sha256::HMAC::mac(data_check_string, secret_key);
+    let hash: String = hash.iter().map(|x| format!("{x:02x}")).collect();
+
+    form_urlencoded::Serializer::new(String::new())
+        .extend_pairs(fields)
+        .append_pair("hash", &hash)
+        .finish()
+}
+
+/// Run a future that never waits for I/O, such as an extractor of a request head.
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let mut cx = Context::from_waker(Waker::noop());
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+    }
+}
@@ -0,0 +1,38 @@
+mod common;
+
+use tg_webapp_init_data::{Error, SharedContact};
+
+const CONTACT: &str = r#"{"user_id":42,"phone_number":"+100","first_name":"Jo"}"#;
+
+#[test]
+fn valid() {
+    let raw = common::sign(&[("contact", CONTACT), ("auth_date", "1700000000")]);
+    let contact = SharedContact::new(common::TOKEN, raw.as_bytes()).unwrap();
+    assert_eq!(contact.user_id(), 42);
+    assert_eq!(contact.phone_number(), "+100");
+    assert_eq!(contact.last_name(), None);
+}
+
+#[test]
+fn signed_as_login_widget() {
+    let raw = common::sign_login_widget(&[("contact", CONTACT), ("auth_date", "1700000000")]);
+    assert!(matches!(
+        SharedContact::new(common::TOKEN, raw.as_bytes()),
+        Err(Error::InvalidHash)
+    ));
+}
+
+#[test]
+fn auth_date_overflow() {
+    let raw = common::sign(&[("contact", CONTACT), ("auth_date", &u64::MAX.to_string())]);
+    assert!(matches!(
+        SharedContact::new(common::TOKEN, raw.as_bytes()),
+        Err(Error::InvalidNumericField("auth_date"))
+    ));
+
+    let json = format!(
+        r#"{{"user_id":42,"phone_number":"+100","first_name":"Jo","auth_date":{}}}"#,
+        u64::MAX
+    );
+    assert!(serde_json::from_str::<SharedContact>(&json).is_err());
+}
@@ -0,0 +1,88 @@
+use tg_webapp_init_data::Error;
+
+fn invalid_json() -> Error {
+    Error::InvalidJson("user", serde_json::from_str::<u32>("{").unwrap_err())
+}
+
+#[test]
+fn display() {
+    assert_eq!(
+        Error::InvalidHash.to_string(),
+        "hash does not match the data"
+    );
+    assert_eq!(
+        Error::MissingField("user").to_string(),
+        "missing field 'user'"
+    );
+    assert_eq!(
+        Error::TooLarge { len: 11, max: 10 }.to_string(),
+        "init data is too large (11 bytes, max is 10)"
+    );
+    assert!(
+        invalid_json()
+            .to_string()
+            .starts_with("invalid json in field 'user': ")
+    );
+}
+
+#[test]
+fn source() {
+    use std::error::Error as _;
+
+    assert!(invalid_json().source().unwrap().is::<serde_json::Error>());
+    assert!(Error::InvalidHash.source().is_none());
+
+    fn validate() -> Result<(), Box<dyn std::error::Error>> {
+        Err(Error::Expired)?
+    }
+    let e = validate().unwrap_err();
+    assert!(matches!(e.downcast_ref::<Error>(), Some(Error::Expired)));
+}
+
+#[test]
+fn classification() {
+    for (e, unauthorized, field) in [
+        (Error::InvalidHash, true, Some("hash")),
+        (Error::MalformedSignature, true, Some("signature")),
+        (Error::Expired, true, Some("auth_date")),
+        (Error::AuthDateInFuture, true, Some("auth_date")),
+        (Error::MissingField("hash"), true, Some("hash")),
+        (Error::MissingField("user"), false, Some("user")),
+        (invalid_json(), false, Some("user")),
+        (
+            Error::InvalidNumericField("auth_date"),
+            false,
+            Some("auth_date"),
+        ),
+        (Error::DuplicateKey("x".to_owned()), false, Some("x")),
+        (Error::EmptyKey, false, None),
+        (Error::TooLarge { len: 11, max: 10 }, false, None),
+    ] {
+        assert_eq!(e.is_unauthorized(), unauthorized, "{e}");
+        assert_eq!(e.field(), field, "{e}");
+    }
+    assert_eq!(Error::InvalidHash.code(), "invalid_hash");
+    assert_eq!(invalid_json().code(), "invalid_json");
+}
+
+#[cfg(feature = "serialize-error")]
+#[test]
+fn serialize() {
+    let json = serde_json::to_value(Error::DuplicateKey("x".to_owned())).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({"code": "duplicate_key", "field": "x", "message": "duplicate key 'x'"})
+    );
+
+    let json = serde_json::to_value(Error::UnknownKey("x".to_owned())).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({"code": "unknown_key", "field": "x", "message": "unknown key 'x'"})
+    );
+
+    let json = serde_json::to_value(Error::EmptyKey).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({"code": "empty_key", "field": null, "message": "empty key"})
+    );
+}
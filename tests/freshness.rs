@@ -0,0 +1,67 @@
+mod common;
+
+use std::time::Duration;
+
+use common::at;
+use tg_webapp_init_data::{Error, ValidationOptions, WebAppInitData};
+
+const AUTH_DATE: u64 = 1_700_000_000;
+
+fn validate(options: ValidationOptions) -> Result<(), Error> {
+    let raw = common::sign(&[("auth_date", &AUTH_DATE.to_string())]);
+    WebAppInitData::new_with_options(common::TOKEN, raw.as_bytes(), &options).map(|_| ())
+}
+
+#[test]
+fn max_age() {
+    let options = ValidationOptions::default().max_age(Duration::from_secs(60));
+    assert!(validate(options.clone().clock(at(AUTH_DATE))).is_ok());
+    assert!(validate(options.clone().clock(at(AUTH_DATE + 60))).is_ok());
+    assert!(matches!(
+        validate(options.clock(at(AUTH_DATE + 61))),
+        Err(Error::Expired)
+    ));
+}
+
+#[test]
+fn max_future_skew() {
+    let options = ValidationOptions::default().max_future_skew(Duration::from_secs(5));
+    assert!(validate(options.clone().clock(at(AUTH_DATE - 5))).is_ok());
+    assert!(matches!(
+        validate(options.clone().clock(at(AUTH_DATE - 6))),
+        Err(Error::AuthDateInFuture)
+    ));
+    // `max_age` alone rejects data from the future.
+    let options = ValidationOptions::default().max_age(Duration::from_secs(60));
+    assert!(validate(options.clone().clock(at(AUTH_DATE))).is_ok());
+    assert!(matches!(
+        validate(options.clone().clock(at(AUTH_DATE - 1))),
+        Err(Error::AuthDateInFuture)
+    ));
+    assert!(
+        validate(
+            options
+                .max_future_skew(Duration::from_secs(3600))
+                .clock(at(AUTH_DATE - 3600))
+        )
+        .is_ok()
+    );
+}
+
+#[test]
+fn unchecked_by_default() {
+    assert!(validate(ValidationOptions::default().clock(at(0))).is_ok());
+    assert!(validate(ValidationOptions::default().clock(at(AUTH_DATE * 2))).is_ok());
+}
+
+#[test]
+fn elapsed_since_auth_at() {
+    let raw = common::sign(&[("auth_date", &AUTH_DATE.to_string())]);
+    let data = WebAppInitData::new(common::TOKEN, raw.as_bytes()).unwrap();
+    assert_eq!(data.auth_date(), at(AUTH_DATE));
+    assert_eq!(
+        data.elapsed_since_auth_at(&at(AUTH_DATE + 10)),
+        Some(Duration::from_secs(10))
+    );
+    assert_eq!(data.elapsed_since_auth_at(&at(AUTH_DATE - 10)), None);
+}
@@ -0,0 +1,160 @@
+#![cfg(feature = "http")]
+
+mod common;
+
+use http::{HeaderName, Request, header};
+use tg_webapp_init_data::http::{ExtractError, InitDataExtractor, InitDataSource};
+use tg_webapp_init_data::{Error, Validator};
+
+fn encode(value: &str) -> String {
+    form_urlencoded::byte_serialize(value.as_bytes()).collect()
+}
+
+#[test]
+fn source_order() {
+    let extractor = InitDataExtractor::empty()
+        .header(HeaderName::from_static("x-init-data"))
+        .query("init_data")
+        .authorization();
+    assert_eq!(
+        extractor.sources(),
+        [
+            InitDataSource::Header(HeaderName::from_static("x-init-data")),
+            InitDataSource::Query("init_data".to_owned()),
+            InitDataSource::Authorization,
+        ]
+    );
+
+    let request = Request::get("/?init_data=query")
+        .header("x-init-data", "header")
+        .header(header::AUTHORIZATION, "tma authorization")
+        .body(())
+        .unwrap();
+    assert_eq!(extractor.extract(&request).as_deref(), Some("header"));
+
+    let request = Request::get("/?init_data=query")
+        .header(header::AUTHORIZATION, "tma authorization")
+        .body(())
+        .unwrap();
+    assert_eq!(extractor.extract(&request).as_deref(), Some("query"));
+
+    let request = Request::get("/?other=query")
+        .header(header::AUTHORIZATION, "tma authorization")
+        .body(())
+        .unwrap();
+    assert_eq!(
+        extractor.extract(&request).as_deref(),
+        Some("authorization")
+    );
+    assert_eq!(
+        extractor.extract_headers(request.headers()).as_deref(),
+        Some("authorization")
+    );
+}
+
+#[test]
+fn default_is_authorization() {
+    let extractor = InitDataExtractor::default();
+    let request = |authorization: &str| {
+        Request::get("/?init_data=query")
+            .header(header::AUTHORIZATION, authorization)
+            .body(())
+            .unwrap()
+    };
+    assert_eq!(
+        extractor.extract(&request("tma a=1")).as_deref(),
+        Some("a=1")
+    );
+    assert_eq!(extractor.extract(&request("Bearer a=1")), None);
+    assert_eq!(extractor.extract(&request("tma")), None);
+}
+
+#[test]
+fn cookie() {
+    let extractor = InitDataExtractor::empty().cookie("init_data");
+    let request = Request::get("/")
+        .header(header::COOKIE, "theme=dark; other_init_data=x")
+        .header(
+            header::COOKIE,
+            "a=b;init_data=user%3D%7B%7D%26hash%3D00 ; c=d",
+        )
+        .body(())
+        .unwrap();
+    assert_eq!(
+        extractor.extract(&request).as_deref(),
+        Some("user={}&hash=00")
+    );
+
+    let request = Request::get("/")
+        .header(header::COOKIE, "init_data_x=1;x_init_data=2")
+        .body(())
+        .unwrap();
+    assert_eq!(extractor.extract(&request), None);
+}
+
+#[test]
+fn body_fields() {
+    let extractor = InitDataExtractor::empty()
+        .form_field("init_data")
+        .json_field("initData");
+    let (parts, ()) = Request::post("/").body(()).unwrap().into_parts();
+
+    let form = format!("a=1&init_data={}", encode("user={}&hash=00"));
+    assert_eq!(
+        extractor
+            .extract_with_body(&parts, form.as_bytes())
+            .as_deref(),
+        Some("user={}&hash=00")
+    );
+    let json = r#"{"a":1,"initData":"user={}&hash=00"}"#;
+    assert_eq!(
+        extractor
+            .extract_with_body(&parts, json.as_bytes())
+            .as_deref(),
+        Some("user={}&hash=00")
+    );
+    assert_eq!(
+        extractor.extract_with_body(&parts, br#"{"initData":1}"#),
+        None
+    );
+
+    // Body sources are skipped without a body.
+    assert_eq!(extractor.extract_parts(&parts), None);
+}
+
+#[test]
+fn validate() {
+    let validator = Validator::new(common::TOKEN);
+    let extractor = InitDataExtractor::empty().query("init_data");
+    let raw = common::sign(&[("auth_date", "1700000000"), ("query_id", "AAH")]);
+    let request = |raw: &str| {
+        Request::get(format!("/?init_data={}", encode(raw)))
+            .body(())
+            .unwrap()
+    };
+
+    let data = extractor.validate(&validator, &request(&raw)).unwrap();
+    assert_eq!(data.query_id(), Some("AAH"));
+
+    let (parts, ()) = request(&raw).into_parts();
+    assert!(extractor.validate_parts(&validator, &parts).is_ok());
+    assert!(
+        extractor
+            .validate_with_body(&validator, &parts, b"")
+            .is_ok()
+    );
+
+    assert!(matches!(
+        extractor.validate(&validator, &Request::get("/").body(()).unwrap()),
+        Err(ExtractError::NotPresent)
+    ));
+    assert!(matches!(
+        extractor.validate(&validator, &request(&raw.replace("AAH", "AAG"))),
+        Err(ExtractError::Invalid(Error::InvalidHash))
+    ));
+    // Present but empty initData is invalid, not missing.
+    assert!(matches!(
+        extractor.validate(&validator, &request("")),
+        Err(ExtractError::Invalid(Error::MissingField(_)))
+    ));
+}
@@ -0,0 +1,125 @@
+mod common;
+
+use std::time::{Duration, SystemTime};
+
+use common::at;
+use tg_webapp_init_data::{ChatType, Error, WebAppInitData};
+
+fn validate(raw: &str) -> WebAppInitData {
+    let Ok(data) = WebAppInitData::new(common::TOKEN, raw.as_bytes()) else {
+        panic!("invalid init data");
+    };
+    data
+}
+
+#[test]
+fn can_send_at() {
+    let raw = common::sign(&[
+        ("query_id", "AAH"),
+        ("auth_date", "1700000000"),
+        ("can_send_after", "10"),
+    ]);
+    let data = validate(&raw);
+    assert_eq!(
+        data.can_send_at(),
+        Some(SystemTime::UNIX_EPOCH + Duration::from_secs(1700000010))
+    );
+    assert!(data.can_send_now());
+    assert!(!data.can_send_now_at(&at(1700000009)));
+    assert!(data.can_send_now_at(&at(1700000010)));
+}
+
+#[test]
+fn can_send_at_requires_query_id() {
+    let raw = common::sign(&[("auth_date", "1700000000")]);
+    let data = validate(&raw);
+    assert_eq!(data.can_send_at(), None);
+    assert!(!data.can_send_now());
+}
+
+#[test]
+fn can_send_after_overflow() {
+    let raw = common::sign(&[
+        ("query_id", "AAH"),
+        ("auth_date", "1700000000"),
+        ("can_send_after", &u64::MAX.to_string()),
+    ]);
+    let data = validate(&raw);
+    assert_eq!(data.can_send_at(), None);
+    assert!(!data.can_send_now());
+}
+
+#[test]
+fn malformed_can_send_after() {
+    let raw = common::sign(&[
+        ("query_id", "AAH"),
+        ("auth_date", "1700000000"),
+        ("can_send_after", "abc"),
+    ]);
+    assert!(matches!(
+        WebAppInitData::new(common::TOKEN, raw.as_bytes()),
+        Err(Error::InvalidNumericField("can_send_after"))
+    ));
+}
+
+#[test]
+fn chat() {
+    let raw = common::sign(&[
+        ("auth_date", "1700000000"),
+        ("chat_type", "supergroup"),
+        ("chat_instance", "-42"),
+        (
+            "chat",
+            r#"{"id":-100,"type":"supergroup","title":"Group","username":"group"}"#,
+        ),
+    ]);
+    let data = validate(&raw);
+    let chat = data.chat().unwrap();
+    assert_eq!(chat.id(), -100);
+    assert_eq!(chat.chat_type(), &ChatType::Supergroup);
+    assert_eq!(chat.title(), "Group");
+    assert_eq!(chat.username(), Some("group"));
+    assert_eq!(chat.photo_url(), None);
+    assert_eq!(data.chat_type(), Some(&ChatType::Supergroup));
+    assert_eq!(data.chat_instance(), Some("-42"));
+}
+
+#[test]
+fn chat_type() {
+    for (raw, chat_type) in [
+        ("sender", ChatType::Sender),
+        ("private", ChatType::Private),
+        ("group", ChatType::Group),
+        ("supergroup", ChatType::Supergroup),
+        ("channel", ChatType::Channel),
+        ("forum", ChatType::Unknown("forum".to_owned())),
+    ] {
+        let data = validate(&common::sign(&[
+            ("auth_date", "1700000000"),
+            ("chat_type", raw),
+        ]));
+        assert_eq!(data.chat_type(), Some(&chat_type));
+        assert_eq!(chat_type.as_str(), raw);
+    }
+}
+
+#[test]
+fn invalid_chat() {
+    let raw = common::sign(&[("auth_date", "1700000000"), ("chat", r#"{"id":1}"#)]);
+    assert!(matches!(
+        WebAppInitData::new(common::TOKEN, raw.as_bytes()),
+        Err(Error::InvalidJson("chat", _))
+    ));
+}
+
+#[test]
+fn auth_date_overflow() {
+    let raw = common::sign(&[("auth_date", &u64::MAX.to_string())]);
+    assert!(matches!(
+        WebAppInitData::new(common::TOKEN, raw.as_bytes()),
+        Err(Error::InvalidNumericField("auth_date"))
+    ));
+
+    let json = format!(r#"{{"auth_date":{}}}"#, u64::MAX);
+    assert!(serde_json::from_str::<WebAppInitData>(&json).is_err());
+}
@@ -0,0 +1,93 @@
+mod common;
+
+use tg_webapp_init_data::{Error, LaunchParams, Platform, Rgb, Validator};
+
+fn init_data_param() -> String {
+    let raw = common::sign(&[("auth_date", "1700000000"), ("start_param", "signed")]);
+    let raw: String = form_urlencoded::byte_serialize(raw.as_bytes()).collect();
+    format!("tgWebAppData={raw}&tgWebAppVersion=8.0&tgWebAppPlatform=ios")
+}
+
+fn parse(url: &str) -> Result<LaunchParams, Error> {
+    LaunchParams::parse(url, &Validator::new(common::TOKEN))
+}
+
+#[test]
+fn fragment() {
+    let params = parse(&format!("https://app.example/#{}", init_data_param())).unwrap();
+    assert_eq!(params.init_data().start_param(), Some("signed"));
+    assert_eq!(params.version().unwrap().to_string(), "8.0");
+    assert_eq!(params.platform(), Some(&Platform::Ios));
+    assert_eq!(params.start_param(), None);
+}
+
+#[test]
+fn bare_list() {
+    let params = parse(&init_data_param()).unwrap();
+    assert_eq!(params.platform(), Some(&Platform::Ios));
+    let params = parse(&format!("#{}", init_data_param())).unwrap();
+    assert_eq!(params.platform(), Some(&Platform::Ios));
+}
+
+#[test]
+fn query_and_fragment() {
+    let url = format!(
+        "https://app.example/path?tgWebAppStartParam=abc#{}",
+        init_data_param()
+    );
+    let params = parse(&url).unwrap();
+    assert_eq!(params.start_param(), Some("abc"));
+    assert_eq!(params.platform(), Some(&Platform::Ios));
+}
+
+#[test]
+fn query_only() {
+    let url = format!(
+        "https://app.example/?{}&tgWebAppStartParam=abc",
+        init_data_param()
+    );
+    let params = parse(&url).unwrap();
+    assert_eq!(params.start_param(), Some("abc"));
+    assert_eq!(params.init_data().start_param(), Some("signed"));
+}
+
+#[test]
+fn fragment_takes_precedence() {
+    let url = format!(
+        "https://app.example/?tgWebAppPlatform=android#{}",
+        init_data_param()
+    );
+    assert_eq!(parse(&url).unwrap().platform(), Some(&Platform::Ios));
+}
+
+#[test]
+fn missing_init_data() {
+    assert!(matches!(
+        parse("https://app.example/?tgWebAppStartParam=abc#tgWebAppVersion=8.0"),
+        Err(Error::MissingField("tgWebAppData"))
+    ));
+}
+
+#[test]
+fn theme_params() {
+    let url = format!(
+        "#{}&tgWebAppThemeParams=%7B%22bg_color%22%3A%22%23ffffff%22%2C%22text_color%22%3A%22black%22%7D",
+        init_data_param()
+    );
+    let theme = *parse(&url).unwrap().theme_params().unwrap();
+    assert_eq!(theme.bg_color(), Some(Rgb::new(255, 255, 255)));
+    assert_eq!(theme.text_color(), None);
+
+    // Unsigned cosmetic parameters do not fail the parsing.
+    let url = format!("#{}&tgWebAppThemeParams=%7B", init_data_param());
+    assert_eq!(parse(&url).unwrap().theme_params(), None);
+}
+
+#[test]
+fn invalid_version() {
+    let url =
+        format!("#{}", init_data_param()).replace("tgWebAppVersion=8.0", "tgWebAppVersion=8.x");
+    let params = parse(&url).unwrap();
+    assert_eq!(params.version(), None);
+    assert_eq!(params.platform(), Some(&Platform::Ios));
+}
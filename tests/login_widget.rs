@@ -0,0 +1,48 @@
+mod common;
+
+use std::time::{Duration, SystemTime};
+
+use tg_webapp_init_data::{Error, LoginWidgetData};
+
+#[test]
+fn valid() {
+    let raw = common::sign_login_widget(&[
+        ("id", "42"),
+        ("first_name", "Jo"),
+        ("username", "jo"),
+        ("auth_date", "1700000000"),
+    ]);
+    let data = LoginWidgetData::new(common::TOKEN, raw.as_bytes()).unwrap();
+    assert_eq!(data.id(), 42);
+    assert_eq!(data.username(), Some("jo"));
+    assert_eq!(
+        data.auth_date(),
+        SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000)
+    );
+    assert_eq!(data.to_user().id(), 42);
+}
+
+#[test]
+fn signed_as_init_data() {
+    let raw = common::sign(&[("id", "42"), ("first_name", "Jo"), ("auth_date", "1")]);
+    assert!(matches!(
+        LoginWidgetData::new(common::TOKEN, raw.as_bytes()),
+        Err(Error::InvalidHash)
+    ));
+}
+
+#[test]
+fn auth_date_overflow() {
+    let raw = common::sign_login_widget(&[
+        ("id", "42"),
+        ("first_name", "Jo"),
+        ("auth_date", &u64::MAX.to_string()),
+    ]);
+    assert!(matches!(
+        LoginWidgetData::new(common::TOKEN, raw.as_bytes()),
+        Err(Error::InvalidNumericField("auth_date"))
+    ));
+
+    let json = format!(r#"{{"id":42,"first_name":"Jo","auth_date":{}}}"#, u64::MAX);
+    assert!(serde_json::from_str::<LoginWidgetData>(&json).is_err());
+}
@@ -0,0 +1,120 @@
+#![cfg(feature = "rocket")]
+
+mod common;
+
+use std::time::Duration;
+
+use rocket::http::{Header, Status};
+use rocket::local::blocking::Client;
+use rocket::{Build, Rocket, get, routes};
+use tg_webapp_init_data::{ValidationOptions, Validator, WebAppInitData, WebAppUser};
+
+#[get("/")]
+fn init_data(data: WebAppInitData) -> String {
+    data.query_id().unwrap_or_default().to_owned()
+}
+
+#[get("/", rank = 2)]
+fn anonymous() -> &'static str {
+    "anonymous"
+}
+
+#[get("/user")]
+fn user(user: WebAppUser) -> String {
+    user.id().to_string()
+}
+
+fn rocket() -> Rocket<Build> {
+    rocket::build()
+        .manage(Validator::new(common::TOKEN))
+        .mount("/", routes![init_data, user])
+}
+
+fn get(client: &Client, path: &str, raw: Option<&str>) -> (Status, Option<String>) {
+    let mut request = client.get(path);
+    if let Some(raw) = raw {
+        request = request.header(Header::new("Authorization", format!("tma {raw}")));
+    }
+    let response = request.dispatch();
+    (response.status(), response.into_string())
+}
+
+#[test]
+fn valid() {
+    let client = Client::tracked(rocket()).unwrap();
+    let raw = common::sign(&[
+        ("auth_date", "1700000000"),
+        ("query_id", "AAH"),
+        ("user", r#"{"id":1,"first_name":"A"}"#),
+    ]);
+    assert_eq!(
+        get(&client, "/", Some(&raw)),
+        (Status::Ok, Some("AAH".to_owned()))
+    );
+    assert_eq!(
+        get(&client, "/user", Some(&raw)),
+        (Status::Ok, Some("1".to_owned()))
+    );
+}
+
+#[test]
+fn missing_header_forwards() {
+    let client = Client::tracked(rocket()).unwrap();
+    assert_eq!(get(&client, "/", None).0, Status::Unauthorized);
+
+    let client = Client::tracked(rocket().mount("/", routes![anonymous])).unwrap();
+    assert_eq!(
+        get(&client, "/", None),
+        (Status::Ok, Some("anonymous".to_owned()))
+    );
+
+    // Invalid initData fails instead of forwarding.
+    let raw = common::sign(&[("auth_date", "1700000000")]).replace("1700000000", "1700000001");
+    assert_eq!(get(&client, "/", Some(&raw)).0, Status::Unauthorized);
+}
+
+#[test]
+fn error_status() {
+    let client = Client::tracked(rocket()).unwrap();
+    let valid = common::sign(&[("auth_date", "1700000000")]);
+    let cases = [
+        (
+            valid.replace("1700000000", "1700000001"),
+            Status::Unauthorized,
+        ),
+        (valid.replace("&hash=", "&hash=0"), Status::Unauthorized),
+        (
+            valid.split("&hash=").next().unwrap().to_owned(),
+            Status::Unauthorized,
+        ),
+        (
+            common::sign(&[("auth_date", "1700000000"), ("user", "{")]),
+            Status::BadRequest,
+        ),
+        (common::sign(&[("auth_date", "x")]), Status::BadRequest),
+        (format!("{valid}&foo=1"), Status::BadRequest),
+    ];
+    for (raw, status) in cases {
+        assert_eq!(get(&client, "/", Some(&raw)).0, status, "{raw}");
+    }
+
+    // The initData lacks the user.
+    assert_eq!(get(&client, "/user", Some(&valid)).0, Status::BadRequest);
+
+    let validator = Validator::new(common::TOKEN)
+        .with_options(ValidationOptions::default().max_age(Duration::from_secs(60)));
+    let client = Client::tracked(
+        rocket::build()
+            .manage(validator)
+            .mount("/", routes![init_data]),
+    )
+    .unwrap();
+    assert_eq!(get(&client, "/", Some(&valid)).0, Status::Unauthorized);
+}
+
+#[test]
+fn missing_validator() {
+    let client = Client::tracked(rocket::build().mount("/", routes![init_data])).unwrap();
+    let raw = common::sign(&[("auth_date", "1700000000")]);
+    assert_eq!(get(&client, "/", Some(&raw)).0, Status::InternalServerError);
+}
@@ -0,0 +1,51 @@
+mod common;
+
+use tg_webapp_init_data::{ChatType, WebAppInitData};
+
+#[test]
+fn round_trip() {
+    let raw = common::sign(&[
+        ("query_id", "AAH"),
+        (
+            "user",
+            r#"{"id":42,"first_name":"Jo","is_premium":false,"allows_write_to_pm":true}"#,
+        ),
+        (
+            "chat",
+            r#"{"id":-100,"type":"supergroup","title":"Group","photo_url":null}"#,
+        ),
+        ("chat_type", "custom"),
+        ("can_send_after", "5"),
+        ("auth_date", "1700000000"),
+    ]);
+    let data = WebAppInitData::new(common::TOKEN, raw.as_bytes()).unwrap();
+    let json = serde_json::to_string(&data).unwrap();
+    assert_eq!(
+        json,
+        concat!(
+            r#"{"query_id":"AAH","user":{"id":42,"first_name":"Jo","allows_write_to_pm":true},"#,
+            r#""chat":{"id":-100,"type":"supergroup","title":"Group"},"chat_type":"custom","#,
+            r#""can_send_after":5,"auth_date":1700000000}"#,
+        )
+    );
+    let parsed: WebAppInitData = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed, data);
+    assert_eq!(
+        parsed.chat_type(),
+        Some(&ChatType::Unknown("custom".to_owned()))
+    );
+}
+
+#[test]
+fn init_data_unsafe() {
+    // `Telegram.WebApp.initDataUnsafe` keeps the numeric fields as strings.
+    let data: WebAppInitData = serde_json::from_str(
+        r#"{"user":{"id":42,"first_name":"Jo"},"can_send_after":"5","auth_date":"1700000000","hash":"00"}"#,
+    )
+    .unwrap();
+    assert_eq!(data.auth_date(), common::at(1_700_000_000));
+    assert_eq!(data.can_send_after().unwrap().as_secs(), 5);
+
+    assert!(serde_json::from_str::<WebAppInitData>(r#"{"auth_date":"soon"}"#).is_err());
+    assert!(serde_json::from_str::<WebAppInitData>(r#"{"auth_date":true}"#).is_err());
+}
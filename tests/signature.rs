@@ -0,0 +1,180 @@
+use tg_webapp_init_data::{Environment, Error, ValidationOptions, Validator, WebAppInitData};
+
+/// initData signed by Telegram for the bot 7342037359.
+const THIRD_PARTY_BOT_ID: u64 = 7342037359;
+const THIRD_PARTY_INIT_DATA: &str = "user=%7B%22id%22%3A279058397%2C%22first_name%22%3A%22Vladislav%20%2B%20-%20%3F%20%5C%2F%22%2C%22last_name%22%3A%22Kibenko%22%2C%22username%22%3A%22vdkfrost%22%2C%22language_code%22%3A%22ru%22%2C%22is_premium%22%3Atrue%2C%22allows_write_to_pm%22%3Atrue%2C%22photo_url%22%3A%22https%3A%5C%2F%5C%2Ft.me%5C%2Fi%5C%2Fuserpic%5C%2F320%5C%2F4FPEE4tmP3ATHa57u6MqTDih13LTOiMoKoLDRG4PnSA.svg%22%7D&chat_instance=8134722200314281151&chat_type=private&auth_date=1733584787&hash=2174df5b000556d044f3f020384e879c8efcab55ddea2ced4eb752e93e7080d6&signature=zL-ucjNyREiHDE8aihFwpfR9aggP2xiAo3NSpfe-p7IbCisNlDKlo7Kb6G4D0Ao2mBrSgEk4maLSdv6MLIlADQ";
+
+/// initData signed with Python's `hmac` module, independently of this crate.
+const TOKEN: &str = "5768337691:AAH5YkoiEuPk8-FZa32hStHTqXiLPtAEhx8";
+const INIT_DATA: &str = "query_id=AAHdF6IQAAAAAN0XohDhrOrc&user=%7B%22id%22%3A279058397%2C%22first_name%22%3A%22Vladislav%22%2C%22last_name%22%3A%22Kibenko%22%2C%22username%22%3A%22vdkfrost%22%2C%22language_code%22%3A%22ru%22%2C%22is_premium%22%3Atrue%7D&auth_date=1662771648&hash=c501b71e775f74ce10e377dea85a7ea24ecd640b223ea86dfe453e0eaed2e2b2";
+
+#[test]
+fn third_party_known_answer() {
+    let Ok(data) = WebAppInitData::new_third_party(
+        THIRD_PARTY_BOT_ID,
+        Environment::Production,
+        THIRD_PARTY_INIT_DATA.as_bytes(),
+    ) else {
+        panic!("invalid init data");
+    };
+    let user = data.user().unwrap();
+    assert_eq!(user.id(), 279058397);
+    assert_eq!(user.first_name(), "Vladislav + - ? /");
+}
+
+#[test]
+fn third_party_rejects_wrong_bot_or_environment() {
+    let raw = THIRD_PARTY_INIT_DATA.as_bytes();
+    assert!(matches!(
+        WebAppInitData::new_third_party(THIRD_PARTY_BOT_ID + 1, Environment::Production, raw),
+        Err(Error::InvalidSignature)
+    ));
+    assert!(matches!(
+        WebAppInitData::new_third_party(THIRD_PARTY_BOT_ID, Environment::Test, raw),
+        Err(Error::InvalidSignature)
+    ));
+}
+
+#[test]
+fn third_party_rejects_tampered_data() {
+    let raw = THIRD_PARTY_INIT_DATA.replace("auth_date=1733584787", "auth_date=1733584788");
+    assert!(matches!(
+        WebAppInitData::new_third_party(
+            THIRD_PARTY_BOT_ID,
+            Environment::Production,
+            raw.as_bytes()
+        ),
+        Err(Error::InvalidSignature)
+    ));
+
+    let raw = THIRD_PARTY_INIT_DATA.replace("&signature=", "&signature=A");
+    assert!(matches!(
+        WebAppInitData::new_third_party(
+            THIRD_PARTY_BOT_ID,
+            Environment::Production,
+            raw.as_bytes()
+        ),
+        Err(Error::MalformedSignature)
+    ));
+}
+
+#[test]
+fn hmac_known_answer() {
+    let Ok(data) = WebAppInitData::new(TOKEN, INIT_DATA.as_bytes()) else {
+        panic!("invalid init data");
+    };
+    assert_eq!(data.user().unwrap().username(), Some("vdkfrost"));
+    assert!(data.user().unwrap().is_premium());
+}
+
+#[test]
+fn hmac_rejects_wrong_token_or_tampered_data() {
+    assert!(matches!(
+        WebAppInitData::new("5768337691:wrong", INIT_DATA.as_bytes()),
+        Err(Error::InvalidHash)
+    ));
+    let raw = INIT_DATA.replace("auth_date=1662771648", "auth_date=1662771649");
+    assert!(matches!(
+        WebAppInitData::new(TOKEN, raw.as_bytes()),
+        Err(Error::InvalidHash)
+    ));
+}
+
+#[test]
+fn hash_format() {
+    let validate = |hash: &str| {
+        let (data, _) = INIT_DATA.split_once("&hash=").unwrap();
+        WebAppInitData::new(TOKEN, format!("{data}&hash={hash}").as_bytes())
+    };
+    let hash = INIT_DATA.split_once("&hash=").unwrap().1;
+
+    assert!(validate(hash).is_ok());
+    assert!(validate(&hash.to_uppercase()).is_ok());
+    assert!(matches!(validate(&hash[..63]), Err(Error::MalformedHash)));
+    assert!(matches!(
+        validate(&format!("{hash}0")),
+        Err(Error::MalformedHash)
+    ));
+    assert!(matches!(
+        validate(&format!("{}g", &hash[..63])),
+        Err(Error::MalformedHash)
+    ));
+    assert!(matches!(validate(""), Err(Error::MalformedHash)));
+
+    // Differs from the right hash only in the last byte.
+    let last = u8::from_str_radix(&hash[62..], 16).unwrap() ^ 1;
+    assert!(matches!(
+        validate(&format!("{}{last:02x}", &hash[..62])),
+        Err(Error::InvalidHash)
+    ));
+
+    let (data, _) = INIT_DATA.split_once("&hash=").unwrap();
+    assert!(matches!(
+        WebAppInitData::new(TOKEN, data.as_bytes()),
+        Err(Error::MissingField("hash"))
+    ));
+}
+
+#[test]
+fn validator_third_party() {
+    let validator = Validator::third_party(THIRD_PARTY_BOT_ID, Environment::Production);
+    let data = validator
+        .validate(THIRD_PARTY_INIT_DATA.as_bytes())
+        .unwrap();
+    assert_eq!(data.chat_instance(), Some("8134722200314281151"));
+    assert_eq!(
+        data,
+        WebAppInitData::new_third_party(
+            THIRD_PARTY_BOT_ID,
+            Environment::Production,
+            THIRD_PARTY_INIT_DATA.as_bytes()
+        )
+        .unwrap()
+    );
+
+    let key = Environment::Test.public_key();
+    assert!(matches!(
+        Validator::third_party_with_key(THIRD_PARTY_BOT_ID, key)
+            .validate(THIRD_PARTY_INIT_DATA.as_bytes()),
+        Err(Error::InvalidSignature)
+    ));
+}
+
+#[test]
+fn third_party_with_options() {
+    let options = ValidationOptions::default().require("query_id");
+    assert!(matches!(
+        WebAppInitData::new_third_party_with_options(
+            THIRD_PARTY_BOT_ID,
+            Environment::Production,
+            THIRD_PARTY_INIT_DATA.as_bytes(),
+            &options,
+        ),
+        Err(Error::MissingField("query_id"))
+    ));
+    let options = ValidationOptions::default().require("chat_instance");
+    assert!(
+        WebAppInitData::new_third_party_with_options(
+            THIRD_PARTY_BOT_ID,
+            Environment::Production,
+            THIRD_PARTY_INIT_DATA.as_bytes(),
+            &options,
+        )
+        .is_ok()
+    );
+}
+
+#[test]
+fn validator_hmac() {
+    let validator = Validator::new(TOKEN);
+    let data = validator.validate(INIT_DATA.as_bytes()).unwrap();
+    assert_eq!(data.query_id(), Some("AAHdF6IQAAAAAN0XohDhrOrc"));
+    assert_eq!(
+        data,
+        WebAppInitData::new(TOKEN, INIT_DATA.as_bytes()).unwrap()
+    );
+    assert!(matches!(
+        Validator::new("5768337691:wrong").validate(INIT_DATA.as_bytes()),
+        Err(Error::InvalidHash)
+    ));
+}
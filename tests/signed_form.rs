@@ -0,0 +1,63 @@
+mod common;
+
+use tg_webapp_init_data::{Error, SignedForm};
+
+#[test]
+fn data_check_string() {
+    let form = SignedForm::parse(b"c=3&hash=00&a=1&signature=sig&b=%3D%26").unwrap();
+    assert_eq!(form.data_check_string(), "a=1\nb==&\nc=3\nsignature=sig");
+    assert_eq!(
+        form.fields().collect::<Vec<_>>(),
+        [
+            ("a", "1"),
+            ("b", "=&"),
+            ("c", "3"),
+            ("hash", "00"),
+            ("signature", "sig"),
+        ]
+    );
+    assert_eq!(form.hash(), Some("00"));
+    assert_eq!(form.signature(), Some("sig"));
+    assert_eq!(form.get("d"), None);
+
+    assert_eq!(SignedForm::parse(b"").unwrap().data_check_string(), "");
+}
+
+#[test]
+fn verify() {
+    let raw = common::sign(&[("b", "2"), ("a", "1")]);
+    let form = SignedForm::parse(raw.as_bytes()).unwrap();
+    form.verify(&SignedForm::web_app_secret_key(common::TOKEN))
+        .unwrap();
+    assert!(matches!(
+        form.verify(&SignedForm::login_widget_secret_key(common::TOKEN)),
+        Err(Error::InvalidHash)
+    ));
+
+    let raw = common::sign_login_widget(&[("id", "42"), ("auth_date", "1")]);
+    let form = SignedForm::parse(raw.as_bytes()).unwrap();
+    form.verify(&SignedForm::login_widget_secret_key(common::TOKEN))
+        .unwrap();
+    assert!(matches!(
+        form.verify(&SignedForm::web_app_secret_key(common::TOKEN)),
+        Err(Error::InvalidHash)
+    ));
+
+    assert!(matches!(
+        SignedForm::parse(b"a=1")
+            .unwrap()
+            .verify(&SignedForm::web_app_secret_key(common::TOKEN)),
+        Err(Error::MissingField("hash"))
+    ));
+}
+
+#[test]
+fn strict_without_known_fields() {
+    let form = SignedForm::parse(b"foo=1&bar=2").unwrap();
+    assert_eq!(form.get("foo"), Some("1"));
+    assert!(matches!(
+        SignedForm::parse(b"foo=1&foo=2"),
+        Err(Error::DuplicateKey(key)) if key == "foo"
+    ));
+    assert!(matches!(SignedForm::parse(b"=1"), Err(Error::EmptyKey)));
+}
@@ -0,0 +1,201 @@
+mod common;
+
+use std::time::Duration;
+
+use common::at;
+use tg_webapp_init_data::{
+    StartParamError, StartParamSigner, Validator, check_start_param, decode_start_param,
+    encode_start_param,
+};
+
+/// The time of the signer's fixed clock, in Unix seconds.
+const NOW: u64 = 1_700_000_000;
+
+fn signer() -> StartParamSigner {
+    StartParamSigner::new(b"server secret").clock(at(NOW))
+}
+
+#[test]
+fn codec_round_trip() {
+    let param = encode_start_param(&("ref", 42)).unwrap();
+    assert_eq!(param, "WyJyZWYiLDQyXQ");
+    assert_eq!(
+        decode_start_param::<(String, u32)>(&param).unwrap(),
+        ("ref".to_owned(), 42)
+    );
+
+    // `+` and `/` in standard base64 are replaced with `-` and `_`.
+    assert_eq!(encode_start_param("a>?b").unwrap(), "ImE-P2Ii");
+    assert_eq!(encode_start_param("??>").unwrap(), "Ij8_PiI");
+    assert_eq!(decode_start_param::<String>("ImE-P2Ii").unwrap(), "a>?b");
+}
+
+#[test]
+fn codec_errors() {
+    assert!(matches!(
+        encode_start_param(&"x".repeat(400)),
+        Err(StartParamError::TooLong { len: 536, max: 512 })
+    ));
+    assert!(matches!(
+        decode_start_param::<String>(&"a".repeat(513)),
+        Err(StartParamError::TooLong { len: 513, max: 512 })
+    ));
+
+    assert!(matches!(
+        decode_start_param::<String>("ImE+P2Ii"),
+        Err(StartParamError::InvalidCharacter {
+            ch: '+',
+            position: 3
+        })
+    ));
+    assert!(matches!(
+        decode_start_param::<String>("Ij8/PiI="),
+        Err(StartParamError::InvalidCharacter {
+            ch: '/',
+            position: 3
+        })
+    ));
+    assert!(matches!(
+        check_start_param("ab\u{e9}"),
+        Err(StartParamError::InvalidCharacter {
+            ch: '\u{e9}',
+            position: 2
+        })
+    ));
+
+    assert!(matches!(
+        decode_start_param::<String>("a"),
+        Err(StartParamError::InvalidEncoding)
+    ));
+    assert!(matches!(
+        decode_start_param::<u32>("ImEi"),
+        Err(StartParamError::InvalidJson(_))
+    ));
+}
+
+#[test]
+fn init_data_decode_start_param() {
+    let param = encode_start_param(&42).unwrap();
+    let raw = common::sign(&[("auth_date", "1700000000"), ("start_param", &param)]);
+    let data = Validator::new(common::TOKEN)
+        .validate(raw.as_bytes())
+        .unwrap();
+    assert_eq!(data.decode_start_param::<u32>().unwrap(), Some(42));
+    assert!(matches!(
+        data.decode_start_param::<String>(),
+        Err(StartParamError::InvalidJson(_))
+    ));
+
+    let raw = common::sign(&[("auth_date", "1700000000"), ("start_param", "a")]);
+    let data = Validator::new(common::TOKEN)
+        .validate(raw.as_bytes())
+        .unwrap();
+    assert!(matches!(
+        data.decode_start_param::<u32>(),
+        Err(StartParamError::InvalidEncoding)
+    ));
+
+    let raw = common::sign(&[("auth_date", "1700000000")]);
+    let data = Validator::new(common::TOKEN)
+        .validate(raw.as_bytes())
+        .unwrap();
+    assert_eq!(data.decode_start_param::<u32>().unwrap(), None);
+}
+
+#[test]
+fn round_trip() {
+    let param = signer()
+        .sign(&("ref", 42), Duration::from_secs(60))
+        .unwrap();
+    assert_eq!(
+        signer().verify::<(String, u32)>(&param).unwrap(),
+        ("ref".to_owned(), 42)
+    );
+}
+
+#[test]
+fn expiry() {
+    let param = signer().sign(&1, Duration::from_secs(60)).unwrap();
+    assert!(signer().clock(at(NOW + 60)).verify::<u32>(&param).is_ok());
+    assert!(matches!(
+        signer().clock(at(NOW + 61)).verify::<u32>(&param),
+        Err(StartParamError::Expired)
+    ));
+}
+
+#[test]
+fn huge_ttl_never_expires() {
+    let param = signer().sign(&1, Duration::MAX).unwrap();
+    assert!(
+        signer()
+            .clock(at(u64::MAX / 4))
+            .verify::<u32>(&param)
+            .is_ok()
+    );
+}
+
+#[test]
+fn forged() {
+    let param = signer().sign(&1, Duration::from_secs(60)).unwrap();
+    assert!(matches!(
+        StartParamSigner::new(b"other secret")
+            .clock(at(NOW))
+            .verify::<u32>(&param),
+        Err(StartParamError::Forged)
+    ));
+
+    // Flip a bit of the expiry.
+    let mut tampered = param.into_bytes();
+    tampered[5] = if tampered[5] == b'A' { b'B' } else { b'A' };
+    assert!(matches!(
+        signer().verify::<u32>(std::str::from_utf8(&tampered).unwrap()),
+        Err(StartParamError::Forged)
+    ));
+}
+
+#[test]
+fn malformed() {
+    assert!(matches!(
+        signer().verify::<u32>("abcd"),
+        Err(StartParamError::Malformed)
+    ));
+    assert!(matches!(
+        signer().verify::<u32>("a"),
+        Err(StartParamError::InvalidEncoding)
+    ));
+    assert!(matches!(
+        signer().verify::<u32>("ab+c"),
+        Err(StartParamError::InvalidCharacter {
+            ch: '+',
+            position: 2
+        })
+    ));
+    assert!(matches!(
+        signer().verify::<u32>(&"a".repeat(513)),
+        Err(StartParamError::TooLong { len: 513, max: 512 })
+    ));
+}
+
+#[test]
+fn payload_too_long() {
+    assert!(matches!(
+        signer().sign(&"x".repeat(400), Duration::from_secs(60)),
+        Err(StartParamError::TooLong { .. })
+    ));
+}
+
+#[test]
+fn verify_start_param() {
+    let param = signer().sign(&42, Duration::from_secs(60)).unwrap();
+    let raw = common::sign(&[("auth_date", "1700000000"), ("start_param", &param)]);
+    let data = Validator::new(common::TOKEN)
+        .validate(raw.as_bytes())
+        .unwrap();
+    assert_eq!(data.verify_start_param::<u32>(&signer()).unwrap(), Some(42));
+
+    let raw = common::sign(&[("auth_date", "1700000000")]);
+    let data = Validator::new(common::TOKEN)
+        .validate(raw.as_bytes())
+        .unwrap();
+    assert_eq!(data.verify_start_param::<u32>(&signer()).unwrap(), None);
+}
@@ -0,0 +1,53 @@
+mod common;
+
+use tg_webapp_init_data::{Error, ValidationOptions, Validator};
+
+fn validate(raw: &str, options: ValidationOptions) -> Result<(), Error> {
+    Validator::new(common::TOKEN)
+        .with_options(options)
+        .validate(raw.as_bytes())
+        .map(|_| ())
+}
+
+#[test]
+fn duplicate_key() {
+    let raw = format!(
+        "auth_date=1&{}",
+        common::sign(&[("auth_date", "1700000000")])
+    );
+    assert!(matches!(
+        validate(&raw, ValidationOptions::default()),
+        Err(Error::DuplicateKey(key)) if key == "auth_date"
+    ));
+    // The last value wins otherwise.
+    assert!(validate(&raw, ValidationOptions::default().strict(false)).is_ok());
+}
+
+#[test]
+fn empty_key() {
+    let raw = format!("{}&=x", common::sign(&[("auth_date", "1700000000")]));
+    assert!(matches!(
+        validate(&raw, ValidationOptions::default()),
+        Err(Error::EmptyKey)
+    ));
+}
+
+#[test]
+fn unknown_key() {
+    let raw = common::sign(&[("auth_date", "1700000000"), ("foo", "bar")]);
+    assert!(matches!(
+        validate(&raw, ValidationOptions::default()),
+        Err(Error::UnknownKey(key)) if key == "foo"
+    ));
+    assert!(validate(&raw, ValidationOptions::default().allow("foo")).is_ok());
+    assert!(validate(&raw, ValidationOptions::default().strict(false)).is_ok());
+}
+
+#[test]
+fn too_large() {
+    let raw = common::sign(&[("auth_date", "1700000000")]);
+    assert!(matches!(
+        validate(&raw, ValidationOptions::default().max_len(10)),
+        Err(Error::TooLarge { max: 10, .. })
+    ));
+}
@@ -0,0 +1,46 @@
+use tg_webapp_init_data::{ColorScheme, Rgb, ThemeParams};
+
+#[test]
+fn rgb() {
+    let white: Rgb = "#FFffff".parse().unwrap();
+    assert_eq!(white, Rgb::new(255, 255, 255));
+    assert_eq!(white.to_string(), "#ffffff");
+    assert_eq!(white.color_scheme(), ColorScheme::Light);
+    assert_eq!(
+        "#17212b".parse::<Rgb>().unwrap().color_scheme(),
+        ColorScheme::Dark
+    );
+
+    for invalid in [
+        "ffffff", "#fffff", "#fffffff", "#gggggg", "#+fffff", "#ffffé",
+    ] {
+        assert!(invalid.parse::<Rgb>().is_err(), "{invalid}");
+    }
+}
+
+#[test]
+fn theme_params() {
+    let theme: ThemeParams = serde_json::from_str(
+        r##"{"bg_color":"#17212b","text_color":"#f5f5f5","new_color":"#000000"}"##,
+    )
+    .unwrap();
+    assert_eq!(theme.bg_color(), Some(Rgb::new(0x17, 0x21, 0x2b)));
+    assert_eq!(theme.color_scheme(), Some(ColorScheme::Dark));
+    assert_eq!(theme.link_color(), None);
+    assert_eq!(
+        serde_json::to_string(&theme).unwrap(),
+        r##"{"bg_color":"#17212b","text_color":"#f5f5f5"}"##
+    );
+}
+
+#[test]
+fn malformed_colors_are_ignored() {
+    let theme: ThemeParams = serde_json::from_str(
+        r##"{"bg_color":"#17212b","text_color":"white","hint_color":null,"link_color":42}"##,
+    )
+    .unwrap();
+    assert_eq!(theme.bg_color(), Some(Rgb::new(0x17, 0x21, 0x2b)));
+    assert_eq!(theme.text_color(), None);
+    assert_eq!(theme.hint_color(), None);
+    assert_eq!(theme.link_color(), None);
+}
@@ -0,0 +1,96 @@
+#![cfg(feature = "tonic")]
+
+mod common;
+
+use tg_webapp_init_data::tonic::{DEFAULT_METADATA_KEY, TelegramInterceptor};
+use tg_webapp_init_data::{Validator, WebAppInitData, WebAppUser};
+use tonic::metadata::{AsciiMetadataKey, MetadataValue};
+use tonic::service::Interceptor;
+use tonic::{Code, Request, Status};
+
+fn call(key: &'static str, raw: Option<&str>) -> Result<Request<()>, Status> {
+    let mut request = Request::new(());
+    if let Some(raw) = raw {
+        request.metadata_mut().insert(key, raw.parse().unwrap());
+    }
+    TelegramInterceptor::new(Validator::new(common::TOKEN)).call(request)
+}
+
+fn error_code(status: &Status) -> &str {
+    status
+        .metadata()
+        .get("x-telegram-auth-error")
+        .unwrap()
+        .to_str()
+        .unwrap()
+}
+
+#[test]
+fn valid() {
+    let raw = common::sign(&[
+        ("auth_date", "1700000000"),
+        ("query_id", "AAH"),
+        ("user", r#"{"id":1,"first_name":"A"}"#),
+    ]);
+    let request = call(DEFAULT_METADATA_KEY, Some(&raw)).unwrap();
+    assert_eq!(request.extensions().get::<WebAppUser>().unwrap().id(), 1);
+    assert_eq!(
+        request
+            .extensions()
+            .get::<WebAppInitData>()
+            .unwrap()
+            .query_id(),
+        Some("AAH")
+    );
+
+    let mut request = Request::new(());
+    request
+        .metadata_mut()
+        .insert("x-init", raw.parse().unwrap());
+    assert!(
+        TelegramInterceptor::new(Validator::new(common::TOKEN))
+            .metadata_key(AsciiMetadataKey::from_static("x-init"))
+            .call(request)
+            .is_ok()
+    );
+}
+
+#[test]
+fn missing_metadata() {
+    let status = call(DEFAULT_METADATA_KEY, None).unwrap_err();
+    assert_eq!(status.code(), Code::Unauthenticated);
+    assert_eq!(status.message(), "missing init data");
+    assert_eq!(error_code(&status), "not_present");
+
+    let raw = common::sign(&[("auth_date", "1700000000")]);
+    let status = call("x-other", Some(&raw)).unwrap_err();
+    assert_eq!(status.message(), "missing init data");
+    assert_eq!(error_code(&status), "not_present");
+
+    let mut request = Request::new(());
+    request
+        .metadata_mut()
+        .insert(DEFAULT_METADATA_KEY, MetadataValue::try_from("é").unwrap());
+    let status = TelegramInterceptor::new(Validator::new(common::TOKEN))
+        .call(request)
+        .unwrap_err();
+    assert_eq!(status.code(), Code::Unauthenticated);
+    assert_eq!(error_code(&status), "malformed");
+}
+
+#[test]
+fn invalid() {
+    let raw = common::sign(&[("auth_date", "1700000000")]).replace("1700000000", "1700000001");
+    let status = call(DEFAULT_METADATA_KEY, Some(&raw)).unwrap_err();
+    assert_eq!(status.code(), Code::Unauthenticated);
+    assert_eq!(error_code(&status), "invalid_hash");
+
+    let raw = common::sign(&[("auth_date", "1700000000"), ("user", "{")]);
+    let status = call(DEFAULT_METADATA_KEY, Some(&raw)).unwrap_err();
+    assert_eq!(error_code(&status), "invalid_json");
+
+    let raw = common::sign(&[("auth_date", "1700000000")]);
+    let status = call(DEFAULT_METADATA_KEY, Some(&raw)).unwrap_err();
+    assert_eq!(status.code(), Code::Unauthenticated);
+    assert_eq!(error_code(&status), "missing_field");
+}
@@ -0,0 +1,129 @@
+#![cfg(feature = "tower")]
+
+mod common;
+
+use std::convert::Infallible;
+use std::future::{Ready, ready};
+use std::task::{Context, Poll};
+
+use http::{Request, Response, StatusCode, header};
+use tg_webapp_init_data::tower::InitDataLayer;
+use tg_webapp_init_data::{Validator, WebAppInitData};
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// Responds with the `query_id` of the verified initData in the request extensions, if any.
+struct Echo;
+
+impl Service<Request<()>> for Echo {
+    type Response = Response<String>;
+    type Error = Infallible;
+    type Future = Ready<Result<Response<String>, Infallible>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Infallible>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: Request<()>) -> Self::Future {
+        let body = req
+            .extensions()
+            .get::<WebAppInitData>()
+            .map(|data| data.query_id().unwrap_or_default().to_owned())
+            .unwrap_or_default();
+        ready(Ok(Response::new(body)))
+    }
+}
+
+fn layer() -> InitDataLayer {
+    InitDataLayer::new(Validator::new(common::TOKEN))
+}
+
+fn call(layer: &InitDataLayer, req: Request<()>) -> Response<String> {
+    let mut service = layer.layer(Echo);
+    common::block_on(service.call(req)).unwrap()
+}
+
+fn status(layer: &InitDataLayer, path: &str) -> StatusCode {
+    call(layer, Request::get(path).body(()).unwrap()).status()
+}
+
+fn authorized(raw: &str) -> Request<()> {
+    Request::get("/")
+        .header(header::AUTHORIZATION, format!("tma {raw}"))
+        .body(())
+        .unwrap()
+}
+
+#[test]
+fn valid_reaches_inner_service() {
+    let raw = common::sign(&[("auth_date", "1700000000"), ("query_id", "AAH")]);
+    let response = call(&layer(), authorized(&raw));
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.body(), "AAH");
+}
+
+#[test]
+fn missing_is_unauthorized() {
+    let response = call(&layer(), Request::get("/").body(()).unwrap());
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(response.headers()[header::WWW_AUTHENTICATE], "tma");
+    assert_eq!(response.body(), "");
+}
+
+#[test]
+fn bad_hash_is_unauthorized() {
+    let raw = common::sign(&[("auth_date", "1700000000"), ("query_id", "AAH")]);
+    let raw = raw.replace("query_id=AAH", "query_id=AAI");
+    let response = call(&layer(), authorized(&raw));
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(response.headers()[header::WWW_AUTHENTICATE], "tma");
+}
+
+#[test]
+fn malformed_is_bad_request() {
+    let raw = common::sign(&[("auth_date", "1700000000"), ("user", "{not json")]);
+    let response = call(&layer(), authorized(&raw));
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert!(!response.headers().contains_key(header::WWW_AUTHENTICATE));
+    assert_eq!(response.body(), "");
+}
+
+#[test]
+fn exempt_prefix_matches_whole_segments() {
+    let layer = self::layer().exempt_prefix("/public");
+    assert_eq!(status(&layer, "/public"), StatusCode::OK);
+    assert_eq!(status(&layer, "/public/logo.png"), StatusCode::OK);
+    assert_eq!(status(&layer, "/publicity/admin"), StatusCode::UNAUTHORIZED);
+    assert_eq!(status(&layer, "/admin"), StatusCode::UNAUTHORIZED);
+
+    let trailing = self::layer().exempt_prefix("/static/");
+    assert_eq!(status(&trailing, "/static/app.js"), StatusCode::OK);
+    assert_eq!(status(&trailing, "/statics"), StatusCode::UNAUTHORIZED);
+}
+
+#[test]
+fn exempt_matches_exact_path() {
+    let layer = layer().exempt("/health");
+    assert_eq!(status(&layer, "/health"), StatusCode::OK);
+    assert_eq!(status(&layer, "/health/db"), StatusCode::UNAUTHORIZED);
+}
+
+#[test]
+fn exempt_rejects_dot_segments() {
+    let layer = layer().exempt("/health").exempt_prefix("/public");
+    for path in [
+        "/public/../admin",
+        "/public/./../admin",
+        "/public/%2e%2e/admin",
+        "/public/%2E%2e/admin",
+        "/public/.%2e/admin",
+        "/public/%2e%2e%2fadmin",
+        "/public/..%5cadmin",
+        "/health/..",
+        "/public/%2e",
+    ] {
+        assert_eq!(status(&layer, path), StatusCode::UNAUTHORIZED, "{path}");
+    }
+    assert_eq!(status(&layer, "/public/app..js"), StatusCode::OK);
+    assert_eq!(status(&layer, "/public/.well-known"), StatusCode::OK);
+}
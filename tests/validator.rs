@@ -0,0 +1,48 @@
+mod common;
+
+use std::time::Duration;
+
+use common::at;
+use tg_webapp_init_data::{Error, ValidationOptions, Validator, WebAppInitData};
+
+#[test]
+fn reusable() {
+    let validator = Validator::new(common::TOKEN);
+    for query_id in ["AAH", "AAI", "AAJ"] {
+        let raw = common::sign(&[("auth_date", "1700000000"), ("query_id", query_id)]);
+        let data = validator.validate(raw.as_bytes()).unwrap();
+        assert_eq!(data.query_id(), Some(query_id));
+        assert_eq!(
+            data,
+            WebAppInitData::new(common::TOKEN, raw.as_bytes()).unwrap()
+        );
+    }
+    let clone = validator.clone();
+    let raw = common::sign(&[("auth_date", "1700000000")]);
+    assert!(clone.validate(raw.as_bytes()).is_ok());
+}
+
+#[test]
+fn with_options() {
+    let options = ValidationOptions::default()
+        .max_age(Duration::from_secs(60))
+        .clock(at(1_700_000_061));
+    let validator = Validator::new(common::TOKEN).with_options(options.clone());
+    assert_eq!(validator.options().now(), at(1_700_000_061));
+
+    let raw = common::sign(&[("auth_date", "1700000000")]);
+    assert!(matches!(
+        validator.validate(raw.as_bytes()),
+        Err(Error::Expired)
+    ));
+    assert!(matches!(
+        WebAppInitData::new_with_options(common::TOKEN, raw.as_bytes(), &options),
+        Err(Error::Expired)
+    ));
+    assert!(
+        Validator::new(common::TOKEN)
+            .with_options(options.clock(at(1_700_000_060)))
+            .validate(raw.as_bytes())
+            .is_ok()
+    );
+}
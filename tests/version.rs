@@ -0,0 +1,68 @@
+use tg_webapp_init_data::{Capability, WebAppVersion};
+
+fn version(s: &str) -> WebAppVersion {
+    s.parse().unwrap()
+}
+
+#[test]
+fn parse() {
+    assert_eq!(version("7.10"), WebAppVersion::new(7, 10));
+    assert_eq!(version("8"), WebAppVersion::new(8, 0));
+    assert_eq!(version("8").to_string(), "8.0");
+    for s in [
+        "", "7.", ".1", "7.1.1", "a.b", "7.a", "-7.1", "+7.1", " 7.1", "7..1", "70000.0",
+    ] {
+        assert!(s.parse::<WebAppVersion>().is_err(), "{s:?}");
+    }
+}
+
+#[test]
+fn numeric_order() {
+    assert!(version("7.10") > version("7.2"));
+    assert!(version("7.2") > version("7.1"));
+    assert!(version("8.0") > version("7.10"));
+    assert_eq!(version("7.02"), version("7.2"));
+}
+
+#[test]
+fn supports() {
+    assert!(!version("7.9").supports(Capability::SecondaryButton));
+    assert!(version("7.10").supports(Capability::SecondaryButton));
+    assert!(version("7.11").supports(Capability::SecondaryButton));
+    assert!(!version("7.10").supports(Capability::Fullscreen));
+    assert!(version("8").supports(Capability::InitDataSignature));
+    assert!(!version("6.0").supports(Capability::BackButton));
+}
+
+#[test]
+fn capabilities() {
+    assert_eq!(version("6.0").capabilities().count(), 0);
+    assert_eq!(
+        version("6.1").capabilities().collect::<Vec<_>>(),
+        [
+            Capability::BackButton,
+            Capability::HapticFeedback,
+            Capability::Invoices
+        ]
+    );
+    let capabilities: Vec<_> = version("7.10").capabilities().collect();
+    assert_eq!(capabilities.last(), Some(&Capability::SecondaryButton));
+    assert!(!capabilities.contains(&Capability::Fullscreen));
+    assert_eq!(
+        version("99.0").capabilities().collect::<Vec<_>>(),
+        Capability::ALL
+    );
+}
+
+#[test]
+fn serde() {
+    assert_eq!(
+        serde_json::to_string(&version("7.10")).unwrap(),
+        r#""7.10""#
+    );
+    assert_eq!(
+        serde_json::from_str::<WebAppVersion>(r#""8""#).unwrap(),
+        version("8.0")
+    );
+    assert!(serde_json::from_str::<WebAppVersion>(r#""7.""#).is_err());
+}